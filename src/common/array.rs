@@ -0,0 +1,64 @@
+use std::{ops::Deref, vec::IntoIter};
+
+use super::object::Object;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Array {
+    values: Vec<Object>,
+    frozen: bool,
+}
+
+impl Array {
+    pub(crate) fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<Object> {
+        self.values
+    }
+}
+
+impl Deref for Array {
+    type Target = Vec<Object>;
+
+    fn deref(&self) -> &Vec<Object> {
+        &self.values
+    }
+}
+
+impl From<Vec<Object>> for Array {
+    fn from(values: Vec<Object>) -> Array {
+        Array {
+            values,
+            frozen: false,
+        }
+    }
+}
+
+impl FromIterator<Object> for Array {
+    fn from_iter<T: IntoIterator<Item = Object>>(values: T) -> Array {
+        Array::from(values.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl IntoIterator for Array {
+    type Item = Object;
+    type IntoIter = IntoIter<Object>;
+
+    fn into_iter(self) -> IntoIter<Object> {
+        self.values.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Array {
+    type Item = &'a Object;
+    type IntoIter = std::slice::Iter<'a, Object>;
+
+    fn into_iter(self) -> std::slice::Iter<'a, Object> {
+        self.values.iter()
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter, Result};
 
-use super::token::Token;
+use super::{bigint::BigInt, decimal::Decimal, token::Token};
 
 #[derive(Debug, Default)]
 pub(crate) struct Program {
@@ -18,39 +18,138 @@ impl Display for Program {
 
 #[derive(Debug, Clone)]
 pub(crate) enum Statement {
-    Let(String, Expression),
-    Mut(String, Expression),
+    Let(Pattern, Expression),
+    Const(Pattern, Expression),
+    Mut(Expression, Expression),
     Return(Expression),
+    Throw(Expression),
+    Defer(Expression),
+    Yield(Expression, bool),
+    Struct(String, Vec<String>),
+    Enum(String, Vec<(String, Vec<String>)>),
     Expression(Expression),
 }
 
 impl Display for Statement {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
-            Statement::Let(identifier, value) => write!(f, "let {identifier} = {value}"),
-            Statement::Mut(identifier, value) => write!(f, "mut {identifier} = {value}"),
+            Statement::Let(pattern, value) => write!(f, "let {pattern} = {value}"),
+            Statement::Const(pattern, value) => write!(f, "const {pattern} = {value}"),
+            Statement::Mut(target, value) => write!(f, "mut {target} = {value}"),
             Statement::Return(value) => write!(f, "return {value}"),
+            Statement::Throw(value) => write!(f, "throw {value}"),
+            Statement::Defer(value) => write!(f, "defer {value}"),
+            Statement::Yield(value, false) => write!(f, "yield {value}"),
+            Statement::Yield(value, true) => write!(f, "yield ...{value}"),
+            Statement::Struct(name, fields) => {
+                write!(f, "struct {name} {{{}}}", comma_separated_values(fields))
+            }
+            Statement::Enum(name, variants) => {
+                let variants = variants
+                    .iter()
+                    .map(|(variant, fields)| match fields.is_empty() {
+                        true => variant.to_owned(),
+                        false => format!("{variant}({})", comma_separated_values(fields)),
+                    })
+                    .collect::<Vec<String>>();
+                write!(f, "enum {name} {{{}}}", variants.join(", "))
+            }
             Statement::Expression(value) => write!(f, "{value}"),
         }
     }
 }
 
+impl Statement {
+    pub(crate) fn yields(&self) -> bool {
+        match self {
+            Statement::Yield(..) => true,
+            Statement::Let(_, value)
+            | Statement::Const(_, value)
+            | Statement::Mut(_, value)
+            | Statement::Return(value)
+            | Statement::Throw(value)
+            | Statement::Defer(value)
+            | Statement::Expression(value) => value.yields(),
+            Statement::Struct(..) | Statement::Enum(..) => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum Expression {
     Identifier(String),
     Integer(i64),
+    BigInteger(BigInt),
     Float(f64),
+    Decimal(Decimal),
     String(String),
     Boolean(bool),
-    Array(Vec<Expression>),
-    Hash(Vec<(Expression, Expression)>),
+    Array(Vec<Element>),
+    Hash(Vec<Entry>),
     Index(Box<Expression>, Box<Expression>),
+    Member(Box<Expression>, String),
+    Propagate(Box<Expression>),
+    Slice(Box<Expression>, Box<Expression>, Box<Expression>),
     Prefix(Token, Box<Expression>),
     Infix(Box<Expression>, Token, Box<Expression>),
+    Range(
+        Box<Expression>,
+        Box<Expression>,
+        Option<Box<Expression>>,
+        bool,
+    ),
     Block(Vec<Statement>),
     If(Box<Expression>, Box<Expression>, Option<Box<Expression>>),
-    Function(Vec<String>, Box<Expression>),
-    Call(Box<Expression>, Vec<Expression>),
+    Function(Vec<Parameter>, Box<Expression>),
+    Call(Box<Expression>, Vec<Argument>),
+    Match(Box<Expression>, Vec<MatchArm>),
+    Try(
+        Box<Expression>,
+        Option<(Option<String>, Box<Expression>)>,
+        Option<Box<Expression>>,
+    ),
+}
+
+impl Expression {
+    /// Whether a `yield` appears in this expression, not counting nested function literals,
+    /// which makes the enclosing function a generator.
+    pub(crate) fn yields(&self) -> bool {
+        match self {
+            Expression::Block(statements) => statements.iter().any(Statement::yields),
+            Expression::If(condition, consequence, alternative) => {
+                condition.yields()
+                    || consequence.yields()
+                    || alternative
+                        .as_ref()
+                        .is_some_and(|alternative| alternative.yields())
+            }
+            Expression::Match(value, arms) => {
+                value.yields()
+                    || arms.iter().any(|arm| {
+                        arm.body.yields() || arm.guard.as_ref().is_some_and(Expression::yields)
+                    })
+            }
+            Expression::Try(body, catch, finally) => {
+                body.yields()
+                    || catch.as_ref().is_some_and(|(_, handler)| handler.yields())
+                    || finally.as_ref().is_some_and(|finally| finally.yields())
+            }
+            Expression::Prefix(_, value) | Expression::Propagate(value) => value.yields(),
+            Expression::Infix(left, _, right) | Expression::Index(left, right) => {
+                left.yields() || right.yields()
+            }
+            Expression::Member(value, _) => value.yields(),
+            Expression::Call(callee, arguments) => {
+                callee.yields()
+                    || arguments.iter().any(|argument| match argument {
+                        Argument::Positional(value)
+                        | Argument::Named(_, value)
+                        | Argument::Spread(value) => value.yields(),
+                    })
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Display for Expression {
@@ -58,14 +157,27 @@ impl Display for Expression {
         match self {
             Expression::Identifier(name) => write!(f, "({name})"),
             Expression::Integer(value) => write!(f, "({value})"),
+            Expression::BigInteger(value) => write!(f, "({value})"),
             Expression::Float(value) => write!(f, "({value})"),
+            Expression::Decimal(value) => write!(f, "({value}d)"),
             Expression::String(value) => write!(f, "({value})"),
             Expression::Boolean(value) => write!(f, "({value})"),
             Expression::Array(values) => write!(f, "([{}])", comma_separated_values(values)),
-            Expression::Hash(pairs) => write!(f, "({{{}}})", comma_separated_pairs(pairs)),
+            Expression::Hash(entries) => write!(f, "({{{}}})", comma_separated_values(entries)),
             Expression::Index(left, index) => write!(f, "({left}[{index}])"),
+            Expression::Member(left, name) => write!(f, "({left}.{name})"),
+            Expression::Propagate(value) => write!(f, "({value}?)"),
+            Expression::Slice(left, range, step) => write!(f, "({left}[{range}:{step}])"),
             Expression::Prefix(operator, right) => write!(f, "({operator} {right})"),
             Expression::Infix(left, operator, right) => write!(f, "({left} {operator} {right})"),
+            Expression::Range(start, end, step, inclusive) => {
+                let operator = if *inclusive { "..=" } else { ".." };
+                write!(f, "({start}{operator}{end}")?;
+                if let Some(step) = step {
+                    write!(f, " step {step}")?;
+                }
+                write!(f, ")")
+            }
             Expression::Block(_) => write!(f, "{{}}"),
             Expression::If(condition, consequence, alternative) => {
                 write!(f, "(if {condition} {consequence}")?;
@@ -76,27 +188,189 @@ impl Display for Expression {
                 }
             }
             Expression::Function(parameters, body) => {
-                write!(f, "(fun({}) {body})", parameters.join(", "))
+                write!(f, "(fun({}) {body})", comma_separated_values(parameters))
             }
             Expression::Call(function, arguments) => {
                 write!(f, "({function}({}))", comma_separated_values(arguments))
             }
+            Expression::Try(body, catch, finally) => {
+                write!(f, "(try {body}")?;
+                match catch {
+                    Some((Some(name), handler)) => write!(f, " catch {name} {handler}")?,
+                    Some((None, handler)) => write!(f, " catch {handler}")?,
+                    None => {}
+                }
+                if let Some(finally) = finally {
+                    write!(f, " finally {finally}")?;
+                }
+                write!(f, ")")
+            }
+            Expression::Match(value, arms) => {
+                write!(f, "(match {value} {{{}}})", comma_separated_values(arms))
+            }
         }
     }
 }
 
-fn comma_separated_values(values: &[Expression]) -> String {
-    values
-        .iter()
-        .map(|value| value.to_string())
-        .collect::<Vec<String>>()
-        .join(", ")
+#[derive(Debug, Clone)]
+pub(crate) struct Parameter {
+    pub(crate) pattern: Pattern,
+    pub(crate) default: Option<Expression>,
+    pub(crate) rest: bool,
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.rest {
+            write!(f, "...")?;
+        }
+        write!(f, "{}", self.pattern)?;
+        if let Some(default) = &self.default {
+            write!(f, " = {default}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Pattern {
+    Wildcard,
+    Identifier(String),
+    Literal(Expression),
+    Range(Expression, Expression, bool),
+    Variant(String, Vec<Pattern>),
+    Array(Vec<Pattern>, Option<String>),
+    Hash(Vec<(String, Pattern)>, Option<String>),
+}
+
+impl Pattern {
+    pub(crate) fn names(&self) -> Vec<&str> {
+        match self {
+            Pattern::Identifier(name) => vec![name],
+            Pattern::Variant(_, patterns) => patterns.iter().flat_map(Pattern::names).collect(),
+            Pattern::Array(patterns, rest) => patterns
+                .iter()
+                .flat_map(Pattern::names)
+                .chain(rest.as_deref())
+                .collect(),
+            Pattern::Hash(entries, rest) => entries
+                .iter()
+                .flat_map(|(_, pattern)| pattern.names())
+                .chain(rest.as_deref())
+                .collect(),
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range(..) => vec![],
+        }
+    }
 }
 
-fn comma_separated_pairs(pairs: &[(Expression, Expression)]) -> String {
-    pairs
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Identifier(name) => write!(f, "{name}"),
+            Pattern::Literal(value) => write!(f, "{value}"),
+            Pattern::Range(start, end, inclusive) => {
+                let operator = if *inclusive { "..=" } else { ".." };
+                write!(f, "{start}{operator}{end}")
+            }
+            Pattern::Variant(name, patterns) => {
+                write!(f, "{name}({})", comma_separated_values(patterns))
+            }
+            Pattern::Array(patterns, rest) => {
+                write!(f, "[{}", comma_separated_values(patterns))?;
+                if let Some(rest) = rest {
+                    let separator = if patterns.is_empty() { "" } else { ", " };
+                    write!(f, "{separator}...{rest}")?;
+                }
+                write!(f, "]")
+            }
+            Pattern::Hash(entries, rest) => {
+                let entries = entries
+                    .iter()
+                    .map(|(key, pattern)| match pattern {
+                        Pattern::Identifier(name) if name == key => key.to_owned(),
+                        pattern => format!("{key}: {pattern}"),
+                    })
+                    .collect::<Vec<String>>();
+                write!(f, "{{{}", entries.join(", "))?;
+                if let Some(rest) = rest {
+                    let separator = if entries.is_empty() { "" } else { ", " };
+                    write!(f, "{separator}...{rest}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MatchArm {
+    pub(crate) pattern: Pattern,
+    pub(crate) guard: Option<Expression>,
+    pub(crate) body: Expression,
+}
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.pattern)?;
+        if let Some(guard) = &self.guard {
+            write!(f, " if {guard}")?;
+        }
+        write!(f, " => {}", self.body)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Argument {
+    Positional(Expression),
+    Named(String, Expression),
+    Spread(Expression),
+}
+
+impl Display for Argument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Argument::Positional(value) => write!(f, "{value}"),
+            Argument::Named(name, value) => write!(f, "{name}: {value}"),
+            Argument::Spread(value) => write!(f, "...{value}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Element {
+    Value(Expression),
+    Spread(Expression),
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Element::Value(value) => write!(f, "{value}"),
+            Element::Spread(value) => write!(f, "...{value}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Entry {
+    Pair(Expression, Expression),
+    Spread(Expression),
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Entry::Pair(key, value) => write!(f, "{key}: {value}"),
+            Entry::Spread(value) => write!(f, "...{value}"),
+        }
+    }
+}
+
+fn comma_separated_values<T: Display>(values: &[T]) -> String {
+    values
         .iter()
-        .map(|pair| format!("{}: {}", pair.0, pair.1))
+        .map(|value| value.to_string())
         .collect::<Vec<String>>()
         .join(", ")
 }
@@ -0,0 +1,337 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub(crate) struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    fn new(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        trim(&mut limbs);
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    pub(crate) fn from_u128(mut magnitude: u128) -> BigInt {
+        let mut limbs = vec![];
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE as u128) as u32);
+            magnitude /= BASE as u128;
+        }
+        BigInt::new(false, limbs)
+    }
+
+    pub(crate) fn power_of_ten(exponent: u32) -> BigInt {
+        let mut limbs = vec![0; exponent as usize / BASE_DIGITS];
+        limbs.push(10u32.pow(exponent % BASE_DIGITS as u32));
+        BigInt::new(false, limbs)
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub(crate) fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub(crate) fn is_even(&self) -> bool {
+        self.limbs.first().is_none_or(|limb| limb % 2 == 0)
+    }
+
+    pub(crate) fn abs(&self) -> BigInt {
+        BigInt::new(false, self.limbs.clone())
+    }
+
+    pub(crate) fn to_i64(&self) -> Option<i64> {
+        let mut magnitude: i128 = 0;
+        for limb in self.limbs.iter().rev() {
+            magnitude = magnitude * BASE as i128 + *limb as i128;
+            if magnitude > i64::MAX as i128 + 1 {
+                return None;
+            }
+        }
+        let value = if self.negative { -magnitude } else { magnitude };
+        i64::try_from(value).ok()
+    }
+
+    pub(crate) fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap()
+    }
+
+    pub(crate) fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.limbs, &other.limbs);
+        Some((
+            BigInt::new(self.negative != other.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        ))
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> BigInt {
+        let magnitude = BigInt::from_u128(value.unsigned_abs() as u128);
+        BigInt::new(value < 0, magnitude.limbs)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add_magnitude(&self.limbs, &other.limbs));
+        }
+        match compare_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt::new(other.negative, sub_magnitude(&other.limbs, &self.limbs)),
+            _ => BigInt::new(self.negative, sub_magnitude(&self.limbs, &other.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            mul_magnitude(&self.limbs, &other.limbs),
+        )
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ();
+
+    fn from_str(source: &str) -> Result<BigInt, ()> {
+        let (negative, digits) = match source.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, source),
+        };
+        if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return Err(());
+        }
+        let mut limbs = vec![];
+        let mut end = digits.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            limbs.push(digits[start..end].parse().map_err(|_| ())?);
+            end = start;
+        }
+        Ok(BigInt::new(negative, limbs))
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.limbs, &other.limbs),
+            (true, true) => compare_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match self.limbs.split_last() {
+            Some((last, rest)) => {
+                write!(f, "{last}")?;
+                for limb in rest.iter().rev() {
+                    write!(f, "{limb:09}")?;
+                }
+                Ok(())
+            }
+            None => write!(f, "0"),
+        }
+    }
+}
+
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn compare_magnitude(left: &[u32], right: &[u32]) -> Ordering {
+    left.len()
+        .cmp(&right.len())
+        .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(left.len().max(right.len()) + 1);
+    let mut carry = 0;
+    for i in 0..left.len().max(right.len()) {
+        let sum = *left.get(i).unwrap_or(&0) as u64 + *right.get(i).unwrap_or(&0) as u64 + carry;
+        result.push((sum % BASE) as u32);
+        carry = sum / BASE;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+fn sub_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(left.len());
+    let mut borrow = 0;
+    for (i, limb) in left.iter().enumerate() {
+        let mut difference = *limb as i64 - *right.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if difference < 0 {
+            difference += BASE as i64;
+            borrow = 1;
+        }
+        result.push(difference as u32);
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+    if left.is_empty() || right.is_empty() {
+        return vec![];
+    }
+    let mut result = vec![0u64; left.len() + right.len()];
+    for (i, x) in left.iter().enumerate() {
+        let mut carry = 0;
+        for (j, y) in right.iter().enumerate() {
+            let product = result[i + j] + *x as u64 * *y as u64 + carry;
+            result[i + j] = product % BASE;
+            carry = product / BASE;
+        }
+        result[i + right.len()] += carry;
+    }
+    let mut result = result.into_iter().map(|limb| limb as u32).collect();
+    trim(&mut result);
+    result
+}
+
+fn div_rem_magnitude(left: &[u32], right: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0; left.len()];
+    let mut remainder: Vec<u32> = vec![];
+    for i in (0..left.len()).rev() {
+        remainder.insert(0, left[i]);
+        trim(&mut remainder);
+        let (mut low, mut high) = (0, BASE - 1);
+        while low < high {
+            let middle = (low + high).div_ceil(2);
+            let product = mul_magnitude(right, &[middle as u32]);
+            if compare_magnitude(&product, &remainder) == Ordering::Greater {
+                high = middle - 1;
+            } else {
+                low = middle;
+            }
+        }
+        if low > 0 {
+            remainder = sub_magnitude(&remainder, &mul_magnitude(right, &[low as u32]));
+        }
+        quotient[i] = low as u32;
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigInt;
+
+    fn big(source: &str) -> BigInt {
+        source.parse().unwrap()
+    }
+
+    #[test]
+    fn div_rem_truncates_toward_zero() {
+        for (left, right, quotient, remainder) in [
+            ("7", "2", "3", "1"),
+            ("-7", "2", "-3", "-1"),
+            ("7", "-2", "-3", "1"),
+            ("-7", "-2", "3", "-1"),
+            ("-6", "3", "-2", "0"),
+            (
+                "1000000000000000000000",
+                "-7",
+                "-142857142857142857142",
+                "6",
+            ),
+        ] {
+            let (q, r) = big(left).div_rem(&big(right)).unwrap();
+            assert_eq!(
+                (q.to_string(), r.to_string()),
+                (quotient.to_string(), remainder.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn div_rem_by_zero_is_none() {
+        assert!(big("5").div_rem(&big("0")).is_none());
+        assert!(big("-5").div_rem(&big("-0")).is_none());
+    }
+
+    #[test]
+    fn to_i64_demotes_within_limits() {
+        assert_eq!(big("9223372036854775807").to_i64(), Some(i64::MAX));
+        assert_eq!(big("-9223372036854775808").to_i64(), Some(i64::MIN));
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+        assert_eq!(big("-9223372036854775809").to_i64(), None);
+        assert_eq!(BigInt::from(i64::MIN).to_string(), "-9223372036854775808");
+        assert_eq!(BigInt::from(i64::MAX).to_i64(), Some(i64::MAX));
+    }
+
+    #[test]
+    fn arithmetic_crosses_the_i64_limits() {
+        let max = BigInt::from(i64::MAX);
+        let min = BigInt::from(i64::MIN);
+        let one = BigInt::from(1);
+        assert_eq!((&max + &one).to_string(), "9223372036854775808");
+        assert_eq!((&(&max + &one) - &one).to_i64(), Some(i64::MAX));
+        assert_eq!((&min - &one).to_string(), "-9223372036854775809");
+        assert_eq!(
+            (&min * &BigInt::from(-1)).to_string(),
+            "9223372036854775808"
+        );
+        assert_eq!(big("-0").to_string(), "0");
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use super::bigint::BigInt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Rounding {
+    HalfEven,
+    HalfUp,
+    HalfDown,
+    Up,
+    Down,
+    Ceiling,
+    Floor,
+}
+
+impl FromStr for Rounding {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Rounding, ()> {
+        match name {
+            "half_even" => Ok(Rounding::HalfEven),
+            "half_up" => Ok(Rounding::HalfUp),
+            "half_down" => Ok(Rounding::HalfDown),
+            "up" => Ok(Rounding::Up),
+            "down" => Ok(Rounding::Down),
+            "ceiling" => Ok(Rounding::Ceiling),
+            "floor" => Ok(Rounding::Floor),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Context {
+    pub(crate) scale: u32,
+    pub(crate) rounding: Rounding,
+}
+
+thread_local! {
+    static CONTEXT: Cell<Context> = const {
+        Cell::new(Context {
+            scale: 16,
+            rounding: Rounding::HalfEven,
+        })
+    };
+}
+
+pub(crate) fn context() -> Context {
+    CONTEXT.get()
+}
+
+pub(crate) fn set_context(context: Context) {
+    CONTEXT.set(context)
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Decimal {
+    coefficient: BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    pub(crate) fn is_zero(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    pub(crate) fn rescale(&self, scale: u32, rounding: Rounding) -> Decimal {
+        if scale >= self.scale {
+            Decimal {
+                coefficient: &self.coefficient * &BigInt::power_of_ten(scale - self.scale),
+                scale,
+            }
+        } else {
+            Decimal {
+                coefficient: divide(
+                    &self.coefficient,
+                    &BigInt::power_of_ten(self.scale - scale),
+                    rounding,
+                ),
+                scale,
+            }
+        }
+    }
+
+    pub(crate) fn div(&self, other: &Decimal, context: Context) -> Option<Decimal> {
+        if other.is_zero() {
+            return None;
+        }
+        let numerator = &self.coefficient * &BigInt::power_of_ten(other.scale + context.scale);
+        let denominator = &other.coefficient * &BigInt::power_of_ten(self.scale);
+        let mut quotient = Decimal {
+            coefficient: divide(&numerator, &denominator, context.rounding),
+            scale: context.scale,
+        };
+        let (preferred, ten) = (self.scale.max(other.scale), BigInt::from(10));
+        while quotient.scale > preferred {
+            match quotient.coefficient.div_rem(&ten) {
+                Some((coefficient, remainder)) if remainder.is_zero() => {
+                    quotient = Decimal {
+                        coefficient,
+                        scale: quotient.scale - 1,
+                    }
+                }
+                _ => break,
+            }
+        }
+        Some(quotient)
+    }
+
+    pub(crate) fn rem(&self, other: &Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let (left, right) = (self.aligned(scale), other.aligned(scale));
+        let (_, remainder) = left.div_rem(&right)?;
+        Some(Decimal {
+            coefficient: remainder,
+            scale,
+        })
+    }
+
+    pub(crate) fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Returns the value as an `i64` if it is a whole number that fits.
+    pub(crate) fn to_i64(&self) -> Option<i64> {
+        let whole = self.rescale(0, Rounding::Down);
+        if whole == *self {
+            whole.coefficient.to_i64()
+        } else {
+            None
+        }
+    }
+
+    fn aligned(&self, scale: u32) -> BigInt {
+        &self.coefficient * &BigInt::power_of_ten(scale - self.scale)
+    }
+}
+
+fn divide(numerator: &BigInt, denominator: &BigInt, rounding: Rounding) -> BigInt {
+    let (quotient, remainder) = numerator.div_rem(denominator).unwrap();
+    if remainder.is_zero() {
+        return quotient;
+    }
+    let negative = numerator.is_negative() != denominator.is_negative();
+    let half = (&remainder + &remainder).abs().cmp(&denominator.abs());
+    let away_from_zero = match rounding {
+        Rounding::Up => true,
+        Rounding::Down => false,
+        Rounding::Ceiling => !negative,
+        Rounding::Floor => negative,
+        Rounding::HalfUp => half.is_ge(),
+        Rounding::HalfDown => half.is_gt(),
+        Rounding::HalfEven => half.is_gt() || (half.is_eq() && !quotient.is_even()),
+    };
+    if away_from_zero {
+        &quotient + &BigInt::from(if negative { -1 } else { 1 })
+    } else {
+        quotient
+    }
+}
+
+impl From<BigInt> for Decimal {
+    fn from(coefficient: BigInt) -> Decimal {
+        Decimal {
+            coefficient,
+            scale: 0,
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ();
+
+    fn from_str(source: &str) -> Result<Decimal, ()> {
+        let (integer, fraction) = source.split_once('.').unwrap_or((source, ""));
+        if integer.trim_start_matches('-').is_empty()
+            || !fraction.bytes().all(|digit| digit.is_ascii_digit())
+        {
+            return Err(());
+        }
+        Ok(Decimal {
+            coefficient: format!("{integer}{fraction}").parse()?,
+            scale: fraction.len() as u32,
+        })
+    }
+}
+
+impl Neg for &Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal {
+            coefficient: -&self.coefficient,
+            scale: self.scale,
+        }
+    }
+}
+
+impl Add for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        Decimal {
+            coefficient: &self.aligned(scale) + &other.aligned(scale),
+            scale,
+        }
+    }
+}
+
+impl Sub for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: &Decimal) -> Decimal {
+        self + &-other
+    }
+}
+
+impl Mul for &Decimal {
+    type Output = Decimal;
+
+    fn mul(self, other: &Decimal) -> Decimal {
+        Decimal {
+            coefficient: &self.coefficient * &other.coefficient,
+            scale: self.scale + other.scale,
+        }
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        self.aligned(scale).cmp(&other.aligned(scale))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.coefficient.abs().to_string();
+        let digits = format!("{digits:0>width$}", width = self.scale as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        if self.coefficient.is_negative() {
+            write!(f, "-")?;
+        }
+        if fraction.is_empty() {
+            write!(f, "{integer}")
+        } else {
+            write!(f, "{integer}.{fraction}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, Rounding};
+
+    fn decimal(source: &str) -> Decimal {
+        source.parse().unwrap()
+    }
+
+    fn rescaled(source: &str, scale: u32, rounding: Rounding) -> String {
+        decimal(source).rescale(scale, rounding).to_string()
+    }
+
+    #[test]
+    fn rounding_modes_on_negative_values() {
+        for (rounding, expected) in [
+            (Rounding::HalfEven, ["-2", "-4", "-3", "-2"]),
+            (Rounding::HalfUp, ["-3", "-4", "-3", "-2"]),
+            (Rounding::HalfDown, ["-2", "-3", "-3", "-2"]),
+            (Rounding::Up, ["-3", "-4", "-3", "-3"]),
+            (Rounding::Down, ["-2", "-3", "-2", "-2"]),
+            (Rounding::Ceiling, ["-2", "-3", "-2", "-2"]),
+            (Rounding::Floor, ["-3", "-4", "-3", "-3"]),
+        ] {
+            let actual = ["-2.5", "-3.5", "-2.6", "-2.4"].map(|value| rescaled(value, 0, rounding));
+            assert_eq!(actual, expected, "{rounding:?}");
+        }
+    }
+
+    #[test]
+    fn rounding_keeps_fractional_scale() {
+        assert_eq!(rescaled("-1.005", 2, Rounding::HalfEven), "-1.00");
+        assert_eq!(rescaled("-1.015", 2, Rounding::HalfEven), "-1.02");
+        assert_eq!(rescaled("-0.001", 2, Rounding::Floor), "-0.01");
+        assert_eq!(rescaled("-0.001", 2, Rounding::Ceiling), "0.00");
+        assert_eq!(rescaled("-1.5", 3, Rounding::Down), "-1.500");
+    }
+}
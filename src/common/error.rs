@@ -1,8 +1,13 @@
+use super::object::Object;
+
 #[derive(Debug)]
 pub(crate) enum Error {
     Lexer(String),
     Parser(String),
     Runtime(String),
+    Thrown(Object),
+    Propagated(Object),
+    Return(Object),
 }
 
 impl Error {
@@ -17,6 +22,12 @@ impl Error {
             Error::Runtime(message) => {
                 eprintln!("RuntimeError: {message}.")
             }
+            Error::Thrown(value) | Error::Propagated(value) => {
+                eprintln!("UncaughtError: {value:#}.")
+            }
+            Error::Return(_) => {
+                eprintln!("RuntimeError: Return is only allowed inside a function.")
+            }
         }
     }
 }
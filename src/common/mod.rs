@@ -1,4 +1,9 @@
+pub(crate) mod array;
 pub(crate) mod ast;
+pub(crate) mod bigint;
+pub(crate) mod decimal;
 pub(crate) mod error;
 pub(crate) mod object;
+pub(crate) mod ordered_hash;
+pub(crate) mod range;
 pub(crate) mod token;
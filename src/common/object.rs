@@ -1,70 +1,314 @@
-use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::discriminant,
+    rc::Rc,
+};
 
-use crate::runtime::environment::Environment;
+use crate::runtime::{environment::Environment, generator::Generator, iterator::Iter};
 
-use super::{ast::Expression, error::Error};
+use super::{
+    array::Array,
+    ast::{Expression, Parameter},
+    bigint::BigInt,
+    decimal::Decimal,
+    error::Error,
+    ordered_hash::OrderedHash,
+    range::Range,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) enum Object {
     Null,
     Boolean(bool),
     Integer(i64),
+    BigInteger(BigInt),
     Float(f64),
+    Decimal(Decimal),
     String(String),
-    Range(i64, i64),
-    Array(Vec<Object>),
-    Hash(HashMap<String, Object>),
-    Return(Box<Object>),
-    Function(Vec<String>, Expression, Rc<RefCell<Environment>>),
+    Range(Range),
+    Array(Array),
+    Hash(OrderedHash),
+    Function(Rc<Function>),
     Builtin(BuiltinFunction),
+    Struct(Rc<Struct>),
+    Record(Rc<Struct>, Vec<Object>),
+    Enum(Rc<Enum>),
+    Variant(Rc<Variant>),
+    Tagged(Rc<Variant>, Vec<Object>),
+    Error(Rc<ErrorValue>),
+    Generator(Rc<RefCell<Generator>>),
+    Iterator(Rc<RefCell<Iter>>),
 }
 
 impl fmt::Display for Object {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.format(f, &|value, f| value.fmt(f))
+    }
+}
+
+impl Object {
+    pub(crate) fn format(
+        &self,
+        f: &mut fmt::Formatter,
+        element: &dyn Fn(&Object, &mut fmt::Formatter) -> fmt::Result,
+    ) -> fmt::Result {
+        let repr = f.alternate();
         match self {
             Object::Null => write!(f, "null"),
             Object::Boolean(value) => write!(f, "{value}"),
             Object::Integer(value) => write!(f, "{value}"),
+            Object::BigInteger(value) => write!(f, "{value}"),
+            Object::Float(value) if repr && value.is_finite() && value.fract() == 0.0 => {
+                write!(f, "{value:.1}")
+            }
             Object::Float(value) => write!(f, "{value}"),
+            Object::Decimal(value) if repr => write!(f, "{value}d"),
+            Object::Decimal(value) => write!(f, "{value}"),
+            Object::String(value) if repr => write!(f, "\"{}\"", escape(value)),
             Object::String(value) => write!(f, "{value}"),
-            Object::Range(from, to) => write!(f, "{from}..{to}"),
+            Object::Range(range) => write!(f, "{range}"),
             Object::Array(values) => {
-                let values = values
-                    .iter()
-                    .map(|v| v.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                write!(f, "[{values}]")
+                if repr {
+                    write!(f, "array")?;
+                }
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element(value, f)?;
+                }
+                write!(f, "]")
             }
             Object::Hash(pairs) => {
-                let pairs = pairs
-                    .iter()
-                    .map(|(k, v)| format!("{k}: {v}"))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                write!(f, "[{pairs}]")
+                if repr {
+                    write!(f, "hash[")?;
+                } else {
+                    write!(f, "{{")?;
+                }
+                for (index, (key, value)) in pairs.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element(key, f)?;
+                    write!(f, ": ")?;
+                    element(value, f)?;
+                }
+                write!(f, "{}", if repr { "]" } else { "}" })
             }
-            Object::Return(value) => write!(f, "{}", *value),
-            Object::Function(params, body, _) => {
-                write!(f, "<function({}) {}>", params.join(", "), body)
+            Object::Function(function) => {
+                write!(
+                    f,
+                    "<function({}) {}>",
+                    function
+                        .parameters
+                        .iter()
+                        .map(|parameter| parameter.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    function.body
+                )
             }
             Object::Builtin(_) => write!(f, "<builtin function>"),
+            Object::Struct(kind) => write!(f, "<struct {}>", kind.name),
+            Object::Record(kind, values) => {
+                write!(f, "{} {{", kind.name)?;
+                for (index, (field, value)) in kind.fields.iter().zip(values).enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {field}: ")?;
+                    element(value, f)?;
+                }
+                if values.is_empty() {
+                    write!(f, "}}")
+                } else {
+                    write!(f, " }}")
+                }
+            }
+            Object::Enum(kind) => write!(f, "<enum {}>", kind.name),
+            Object::Error(error) => write!(f, "{}: {}", error.kind, error.message),
+            Object::Generator(_) => write!(f, "<generator>"),
+            Object::Iterator(_) => write!(f, "<iterator>"),
+            Object::Variant(variant) => {
+                write!(f, "<variant {}.{}>", variant.enumeration, variant.name)
+            }
+            Object::Tagged(variant, values) if values.is_empty() => write!(f, "{}", variant.name),
+            Object::Tagged(variant, values) => {
+                write!(f, "{}(", variant.name)?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element(value, f)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            character => escaped.push(character),
         }
     }
+    escaped
 }
 
 impl Object {
+    /// Arrays, hashes, ranges, records and enum values compare structurally, functions,
+    /// builtins, structs and enums by identity. Records are only equal to records of the
+    /// same struct, enum values only to values of the same variant.
+    /// Floats follow IEEE 754, so `NaN` is not equal to anything, itself included, and a
+    /// collection holding `NaN` is not equal to itself either.
     pub(crate) fn equal(&self, other: &Object) -> bool {
         match (self, other) {
             (Object::Null, Object::Null) => true,
             (Object::Boolean(x), Object::Boolean(y)) => x == y,
             (Object::Integer(x), Object::Integer(y)) => x == y,
+            (Object::BigInteger(x), Object::BigInteger(y)) => x == y,
             (Object::Float(x), Object::Float(y)) => x == y,
+            (Object::Decimal(x), Object::Decimal(y)) => x == y,
             (Object::String(x), Object::String(y)) => x == y,
+            (Object::Range(x), Object::Range(y)) => x == y,
+            (Object::Array(x), Object::Array(y)) => {
+                x.len() == y.len() && x.iter().zip(y).all(|(x, y)| x.equal(y))
+            }
+            (Object::Hash(x), Object::Hash(y)) => {
+                x.len() == y.len()
+                    && x.iter()
+                        .all(|(key, x)| y.get(key).is_some_and(|y| x.equal(y)))
+            }
+            (Object::Function(x), Object::Function(y)) => Rc::ptr_eq(x, y),
+            (Object::Builtin(x), Object::Builtin(y)) => std::ptr::fn_addr_eq(*x, *y),
+            (Object::Struct(x), Object::Struct(y)) => Rc::ptr_eq(x, y),
+            (Object::Record(x, xs), Object::Record(y, ys)) => {
+                Rc::ptr_eq(x, y) && xs.iter().zip(ys).all(|(x, y)| x.equal(y))
+            }
+            (Object::Enum(x), Object::Enum(y)) => Rc::ptr_eq(x, y),
+            (Object::Generator(x), Object::Generator(y)) => Rc::ptr_eq(x, y),
+            (Object::Iterator(x), Object::Iterator(y)) => Rc::ptr_eq(x, y),
+            (Object::Variant(x), Object::Variant(y)) => Rc::ptr_eq(x, y),
+            (Object::Tagged(x, xs), Object::Tagged(y, ys)) => {
+                Rc::ptr_eq(x, y) && xs.iter().zip(ys).all(|(x, y)| x.equal(y))
+            }
+            (Object::Error(x), Object::Error(y)) => {
+                x.kind == y.kind && x.message == y.message && x.data.equal(&y.data)
+            }
+            (
+                Object::Integer(_) | Object::BigInteger(_) | Object::Float(_) | Object::Decimal(_),
+                Object::Integer(_) | Object::BigInteger(_) | Object::Float(_) | Object::Decimal(_),
+            ) => self.compare(other).is_ok_and(Ordering::is_eq),
             _ => false,
         }
     }
 
+    /// Numbers order by value across integers, floats and decimals, except that decimals
+    /// and floats are never mixed. Strings order by code point, arrays lexicographically
+    /// and `false` before `true`. `NaN` and values of unrelated kinds are not comparable.
+    pub(crate) fn compare(&self, other: &Object) -> Result<Ordering, Error> {
+        let ordering = match (self, other) {
+            (Object::Null, Object::Null) => Some(Ordering::Equal),
+            (Object::Boolean(x), Object::Boolean(y)) => Some(x.cmp(y)),
+            (Object::Integer(x), Object::Integer(y)) => Some(x.cmp(y)),
+            (Object::Float(x), Object::Float(y)) => x.partial_cmp(y),
+            (Object::Float(x), y @ (Object::Integer(_) | Object::BigInteger(_))) => {
+                compare_float(*x, y)
+            }
+            (x @ (Object::Integer(_) | Object::BigInteger(_)), Object::Float(y)) => {
+                compare_float(*y, x).map(Ordering::reverse)
+            }
+            (Object::String(x), Object::String(y)) => Some(x.cmp(y)),
+            (Object::Array(x), Object::Array(y)) => {
+                for (x, y) in x.iter().zip(y) {
+                    match x.compare(y)? {
+                        Ordering::Equal => continue,
+                        ordering => return Ok(ordering),
+                    }
+                }
+                Some(x.len().cmp(&y.len()))
+            }
+            (x, y) => match (x.to_decimal(), y.to_decimal()) {
+                (Some(x), Some(y)) => Some(x.cmp(&y)),
+                _ => None,
+            },
+        };
+        ordering.ok_or(Error::Runtime(format!(
+            "Can not compare '{self}' and '{other}'"
+        )))
+    }
+
+    pub(crate) fn is_hashable(&self) -> bool {
+        match self {
+            Object::Float(value) => !value.is_nan(),
+            Object::Array(values) => values.iter().all(Object::is_hashable),
+            Object::Record(_, values) | Object::Tagged(_, values) => {
+                values.iter().all(Object::is_hashable)
+            }
+            Object::Error(error) => error.data.is_hashable(),
+            Object::Hash(_) => false,
+            _ => true,
+        }
+    }
+
+    pub(crate) fn to_f64(&self) -> Option<f64> {
+        match self {
+            Object::Integer(value) => Some(*value as f64),
+            Object::BigInteger(value) => Some(value.to_f64()),
+            Object::Float(value) => Some(*value),
+            Object::Decimal(value) => Some(value.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Returns a number as an `i64` if it is a whole number that fits, so `2.0` and `2.00`
+    /// give `2` and `2.5` gives nothing.
+    pub(crate) fn to_i64(&self) -> Option<i64> {
+        match self {
+            Object::Integer(value) => Some(*value),
+            Object::BigInteger(value) => value.to_i64(),
+            Object::Float(value)
+                if value.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(value) =>
+            {
+                Some(*value as i64)
+            }
+            Object::Decimal(value) => value.to_i64(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_big_integer(&self) -> Option<BigInt> {
+        match self {
+            Object::Integer(value) => Some(BigInt::from(*value)),
+            Object::BigInteger(value) => Some(value.to_owned()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_decimal(&self) -> Option<Decimal> {
+        match self {
+            Object::Decimal(value) => Some(value.to_owned()),
+            object => object.to_big_integer().map(Decimal::from),
+        }
+    }
+
+    pub(crate) fn repr(&self) -> String {
+        format!("{self:#}")
+    }
+
     pub(crate) fn is_truthy(&self) -> bool {
         match self {
             Object::Null => false,
@@ -74,4 +318,135 @@ impl Object {
     }
 }
 
+/// Compares a float with an integer exactly, without rounding the integer to a float.
+fn compare_float(x: f64, y: &Object) -> Option<Ordering> {
+    if x.is_nan() {
+        return None;
+    }
+    if x.is_infinite() {
+        return Some(if x > 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        });
+    }
+    let whole = x.trunc();
+    let integer = if whole.abs() < i64::MAX as f64 {
+        BigInt::from(whole as i64)
+    } else {
+        format!("{whole:.0}").parse().ok()?
+    };
+    Some(
+        integer
+            .cmp(&y.to_big_integer()?)
+            .then(x.partial_cmp(&whole)?),
+    )
+}
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Object) -> bool {
+        self.equal(other)
+    }
+}
+
+impl Eq for Object {}
+
+impl Hash for Object {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Some(value) = self.to_f64() {
+            (value + 0.0).to_bits().hash(state);
+            return;
+        }
+        discriminant(self).hash(state);
+        match self {
+            Object::Boolean(value) => value.hash(state),
+            Object::String(value) => value.hash(state),
+            Object::Range(range) => range.hash(state),
+            Object::Array(values) => values.hash(state),
+            Object::Hash(pairs) => pairs.len().hash(state),
+            Object::Function(function) => Rc::as_ptr(function).hash(state),
+            Object::Builtin(function) => (*function as usize).hash(state),
+            Object::Struct(kind) => Rc::as_ptr(kind).hash(state),
+            Object::Record(kind, values) => {
+                Rc::as_ptr(kind).hash(state);
+                values.hash(state);
+            }
+            Object::Enum(kind) => Rc::as_ptr(kind).hash(state),
+            Object::Generator(generator) => Rc::as_ptr(generator).hash(state),
+            Object::Iterator(iterator) => Rc::as_ptr(iterator).hash(state),
+            Object::Variant(variant) => Rc::as_ptr(variant).hash(state),
+            Object::Tagged(variant, values) => {
+                Rc::as_ptr(variant).hash(state);
+                values.hash(state);
+            }
+            Object::Error(error) => {
+                error.kind.hash(state);
+                error.message.hash(state);
+                error.data.hash(state);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl From<BigInt> for Object {
+    fn from(value: BigInt) -> Object {
+        match value.to_i64() {
+            Some(value) => Object::Integer(value),
+            None => Object::BigInteger(value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Function {
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) body: Expression,
+    pub(crate) environment: Rc<RefCell<Environment>>,
+    pub(crate) generator: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct Struct {
+    pub(crate) name: String,
+    pub(crate) fields: Vec<String>,
+    pub(crate) methods: RefCell<HashMap<String, Object>>,
+}
+
+impl Struct {
+    pub(crate) fn position(&self, field: &str) -> Option<usize> {
+        self.fields.iter().position(|name| name == field)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Enum {
+    pub(crate) name: String,
+    pub(crate) variants: Vec<Object>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Variant {
+    pub(crate) enumeration: String,
+    pub(crate) name: String,
+    pub(crate) arity: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct ErrorValue {
+    pub(crate) kind: String,
+    pub(crate) message: String,
+    pub(crate) data: Object,
+}
+
+impl ErrorValue {
+    pub(crate) fn new(kind: &str, message: String, data: Object) -> ErrorValue {
+        ErrorValue {
+            kind: kind.to_string(),
+            message,
+            data,
+        }
+    }
+}
+
 pub(crate) type BuiltinFunction = fn(Vec<Object>) -> Result<Object, Error>;
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use super::object::Object;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OrderedHash {
+    entries: Vec<(Object, Object)>,
+    indices: HashMap<Object, usize>,
+    frozen: bool,
+}
+
+impl OrderedHash {
+    pub(crate) fn new() -> OrderedHash {
+        OrderedHash::default()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn get(&self, key: &Object) -> Option<&Object> {
+        self.indices.get(key).map(|index| &self.entries[*index].1)
+    }
+
+    pub(crate) fn insert(&mut self, key: Object, value: Object) {
+        match self.indices.get(&key) {
+            Some(index) => self.entries[*index].1 = value,
+            None => {
+                self.indices.insert(key.to_owned(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    pub(crate) fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Object, &Object)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &Object> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &Object> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Range {
+    pub(crate) start: i64,
+    pub(crate) end: i64,
+    pub(crate) step: Option<i64>,
+    pub(crate) inclusive: bool,
+}
+
+impl Range {
+    pub(crate) fn step(&self) -> i64 {
+        self.step
+            .unwrap_or(if self.start <= self.end { 1 } else { -1 })
+    }
+
+    pub(crate) fn len(&self) -> u128 {
+        let (start, end, step) = (self.start as i128, self.end as i128, self.step() as i128);
+        let end = match (self.inclusive, step > 0) {
+            (false, _) => end,
+            (true, true) => end + 1,
+            (true, false) => end - 1,
+        };
+        let distance = if step > 0 { end - start } else { start - end };
+        if step == 0 || distance <= 0 {
+            0
+        } else {
+            ((distance + step.abs() - 1) / step.abs()) as u128
+        }
+    }
+
+    pub(crate) fn get(&self, index: u128) -> Option<i64> {
+        if index < self.len() {
+            Some(self.position(index))
+        } else {
+            None
+        }
+    }
+
+    fn position(&self, index: u128) -> i64 {
+        (self.start as i128 + index as i128 * self.step() as i128) as i64
+    }
+
+    pub(crate) fn first(&self) -> Option<i64> {
+        self.get(0)
+    }
+
+    pub(crate) fn last(&self) -> Option<i64> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    pub(crate) fn contains(&self, value: i64) -> bool {
+        let (offset, step) = (value as i128 - self.start as i128, self.step() as i128);
+        step != 0 && offset % step == 0 && (0..self.len() as i128).contains(&(offset / step))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = i64> {
+        let range = *self;
+        (0..self.len()).map(move |index| range.position(index))
+    }
+}
+
+impl PartialEq for Range {
+    fn eq(&self, other: &Range) -> bool {
+        self.len() == other.len()
+            && self.first() == other.first()
+            && (self.len() < 2 || self.step() == other.step())
+    }
+}
+
+impl Eq for Range {}
+
+impl Hash for Range {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        self.first().hash(state);
+        if self.len() > 1 {
+            self.step().hash(state);
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operator = if self.inclusive { "..=" } else { ".." };
+        write!(f, "{}{operator}{}", self.start, self.end)?;
+        if let Some(step) = self.step {
+            write!(f, " step {step}")?;
+        }
+        Ok(())
+    }
+}
@@ -7,17 +7,23 @@ pub(crate) enum Token {
     Identifier(String),
     Integer(String),
     Float(String),
+    Decimal(String),
     String(String),
 
     Assign,
+    FatArrow,
 
     Spread,
+    SpreadInclusive,
+    Ellipsis,
 
     Plus,
     Minus,
     Bang,
     Asterisk,
     Slash,
+    Percent,
+    Question,
 
     Lesser,
     LesserOrEqual,
@@ -44,6 +50,7 @@ pub(crate) enum Token {
     RightBracket,
 
     Let,
+    Const,
     Mut,
     If,
     Else,
@@ -52,6 +59,18 @@ pub(crate) enum Token {
     False,
     Array,
     Hash,
+    Step,
+    In,
+    Not,
+    Match,
+    Struct,
+    Enum,
+    Throw,
+    Try,
+    Catch,
+    Finally,
+    Defer,
+    Yield,
 }
 
 impl fmt::Display for Token {
@@ -62,16 +81,22 @@ impl fmt::Display for Token {
             Token::Identifier(identifier) => write!(f, "{identifier}"),
             Token::Integer(int) => write!(f, "{int}"),
             Token::Float(float) => write!(f, "{float}"),
+            Token::Decimal(decimal) => write!(f, "{decimal}d"),
             Token::String(s) => write!(f, "\"{s}\""),
 
             Token::Assign => write!(f, "="),
+            Token::FatArrow => write!(f, "=>"),
 
             Token::Spread => write!(f, ".."),
+            Token::SpreadInclusive => write!(f, "..="),
+            Token::Ellipsis => write!(f, "..."),
             Token::Plus => write!(f, "+"),
             Token::Minus => write!(f, "-"),
             Token::Bang => write!(f, "!"),
             Token::Asterisk => write!(f, "*"),
             Token::Slash => write!(f, "/"),
+            Token::Percent => write!(f, "%"),
+            Token::Question => write!(f, "?"),
 
             Token::Lesser => write!(f, "<"),
             Token::LesserOrEqual => write!(f, "<="),
@@ -98,6 +123,7 @@ impl fmt::Display for Token {
             Token::RightBracket => write!(f, "]"),
 
             Token::Let => write!(f, "let"),
+            Token::Const => write!(f, "const"),
             Token::Mut => write!(f, "mut"),
             Token::True => write!(f, "true"),
             Token::False => write!(f, "false"),
@@ -106,6 +132,18 @@ impl fmt::Display for Token {
             Token::Return => write!(f, "return"),
             Token::Array => write!(f, "array"),
             Token::Hash => write!(f, "hash"),
+            Token::Step => write!(f, "step"),
+            Token::In => write!(f, "in"),
+            Token::Not => write!(f, "not"),
+            Token::Match => write!(f, "match"),
+            Token::Struct => write!(f, "struct"),
+            Token::Enum => write!(f, "enum"),
+            Token::Throw => write!(f, "throw"),
+            Token::Try => write!(f, "try"),
+            Token::Catch => write!(f, "catch"),
+            Token::Finally => write!(f, "finally"),
+            Token::Defer => write!(f, "defer"),
+            Token::Yield => write!(f, "yield"),
         }
     }
 }
@@ -113,6 +151,7 @@ impl fmt::Display for Token {
 pub(crate) fn lookup_identifier(identifier: &str) -> Token {
     match identifier {
         "let" => Token::Let,
+        "const" => Token::Const,
         "mut" => Token::Mut,
         "if" => Token::If,
         "else" => Token::Else,
@@ -121,6 +160,18 @@ pub(crate) fn lookup_identifier(identifier: &str) -> Token {
         "false" => Token::False,
         "array" => Token::Array,
         "hash" => Token::Hash,
+        "step" => Token::Step,
+        "in" => Token::In,
+        "not" => Token::Not,
+        "match" => Token::Match,
+        "struct" => Token::Struct,
+        "enum" => Token::Enum,
+        "throw" => Token::Throw,
+        "try" => Token::Try,
+        "catch" => Token::Catch,
+        "finally" => Token::Finally,
+        "defer" => Token::Defer,
+        "yield" => Token::Yield,
         _ => Token::Identifier(identifier.to_owned()),
     }
 }
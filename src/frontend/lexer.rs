@@ -14,7 +14,11 @@ pub(crate) struct Lexer {
 
 impl Lexer {
     pub(crate) fn new(source: String) -> Lexer {
-        let chars = unsafe { std::mem::transmute(source.chars().peekable()) };
+        let chars = unsafe {
+            std::mem::transmute::<Peekable<Chars<'_>>, Peekable<Chars<'static>>>(
+                source.chars().peekable(),
+            )
+        };
         let mut lexer = Lexer {
             source,
             position: 0,
@@ -48,6 +52,9 @@ impl Lexer {
                 if self.peek_char() == '=' {
                     self.read_next_character();
                     token = Token::Equal
+                } else if self.peek_char() == '>' {
+                    self.read_next_character();
+                    token = Token::FatArrow
                 } else {
                     token = Token::Assign
                 }
@@ -55,12 +62,21 @@ impl Lexer {
             '.' => {
                 if self.peek_char() == '.' {
                     self.read_next_character();
-                    token = Token::Spread
+                    if self.peek_char() == '=' {
+                        self.read_next_character();
+                        token = Token::SpreadInclusive
+                    } else if self.peek_char() == '.' {
+                        self.read_next_character();
+                        token = Token::Ellipsis
+                    } else {
+                        token = Token::Spread
+                    }
                 } else {
                     token = Token::Dot
                 }
             }
             '+' => token = Token::Plus,
+            '?' => token = Token::Question,
             '-' => token = Token::Minus,
             '!' => {
                 if self.peek_char() == '=' {
@@ -72,6 +88,7 @@ impl Lexer {
             }
             '*' => token = Token::Asterisk,
             '/' => token = Token::Slash,
+            '%' => token = Token::Percent,
             '<' => {
                 if self.peek_char() == '=' {
                     self.read_next_character();
@@ -119,13 +136,26 @@ impl Lexer {
                     return Ok(lookup_identifier(identifier));
                 } else if is_digit(character) {
                     let integer = self.read_number();
+                    let mut fraction = None;
                     if self.character == '.' && is_digit(self.peek_char()) {
                         self.read_next_character();
-                        let fraction = self.read_number();
-                        return Ok(Token::Float(format!("{integer}.{fraction}")));
-                    } else {
-                        return Ok(Token::Integer(integer));
+                        fraction = Some(self.read_number());
                     }
+                    let number = match &fraction {
+                        Some(fraction) => format!("{integer}.{fraction}"),
+                        None => integer,
+                    };
+                    if self.character == 'd'
+                        && !is_letter(self.peek_char())
+                        && !is_digit(self.peek_char())
+                    {
+                        self.read_next_character();
+                        return Ok(Token::Decimal(number));
+                    }
+                    return Ok(match fraction {
+                        Some(_) => Token::Float(number),
+                        None => Token::Integer(number),
+                    });
                 } else {
                     return Err(Error::Lexer(format!("Unexpected character '{character}'")));
                 }
@@ -159,18 +189,32 @@ impl Lexer {
     }
 
     fn read_string(&mut self) -> Result<String, Error> {
-        let position = self.position + 1;
+        let mut string = String::new();
         loop {
             self.read_next_character();
-            if self.character == '\u{0}' || self.character == '"' {
-                break;
+            match self.character {
+                '\u{0}' => return Err(Error::Lexer("Unterminated string".to_string())),
+                '"' => return Ok(string),
+                '\\' => {
+                    self.read_next_character();
+                    let escaped = match self.character {
+                        '"' => '"',
+                        '\\' => '\\',
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '\u{0}' => return Err(Error::Lexer("Unterminated string".to_string())),
+                        character => {
+                            string.push('\\');
+                            character
+                        }
+                    };
+                    string.push(escaped);
+                }
+                character => string.push(character),
             }
         }
-        if self.character == '\u{0}' {
-            Err(Error::Lexer("Unterminated string".to_string()))
-        } else {
-            Ok(self.source[position..self.position].to_string())
-        }
     }
 
     fn read_next_character(&mut self) {
@@ -188,11 +232,11 @@ impl Lexer {
 }
 
 fn is_letter(character: char) -> bool {
-    character == '_' || ('a'..='z').contains(&character) || ('A'..='Z').contains(&character)
+    character == '_' || character.is_ascii_alphabetic()
 }
 
 fn is_digit(character: char) -> bool {
-    ('0'..='9').contains(&character)
+    character.is_ascii_digit()
 }
 
 fn is_whitespace(character: char) -> bool {
@@ -1,5 +1,5 @@
 use crate::common::{
-    ast::{Expression, Program, Statement},
+    ast::{Argument, Element, Entry, Expression, MatchArm, Parameter, Pattern, Program, Statement},
     error::Error,
     token::Token,
 };
@@ -28,40 +28,112 @@ impl Parser {
     fn parse_statement(&mut self) -> Result<Statement, Error> {
         match self.current_token() {
             Token::Let => self.parse_let_statement(),
+            Token::Const => self.parse_const_statement(),
             Token::Mut => self.parse_mut_statement(),
             Token::Return => self.parse_return_statement(),
+            Token::Throw => self.parse_throw_statement(),
+            Token::Defer => self.parse_defer_statement(),
+            Token::Yield => self.parse_yield_statement(),
+            Token::Struct => self.parse_struct_statement(),
+            Token::Enum => self.parse_enum_statement(),
             _ => self.parse_expression_statement(),
         }
     }
 
     fn parse_let_statement(&mut self) -> Result<Statement, Error> {
         self.expect_token(Token::Let)?;
-        if let Token::Identifier(identifier) = self.current_token().to_owned() {
-            self.advance_position();
-            self.expect_token(Token::Assign)?;
-            let value = self.parse_expression()?;
-            Ok(Statement::Let(identifier, value))
-        } else {
-            Err(Error::Parser(format!(
-                "Unexpected token '{}', expected IDENTIFIER",
-                self.current_token(),
-            )))
-        }
+        let pattern = self.parse_pattern()?;
+        self.expect_token(Token::Assign)?;
+        let value = self.parse_expression()?;
+        Ok(Statement::Let(pattern, value))
+    }
+
+    fn parse_const_statement(&mut self) -> Result<Statement, Error> {
+        self.expect_token(Token::Const)?;
+        let pattern = self.parse_pattern()?;
+        self.expect_token(Token::Assign)?;
+        let value = self.parse_expression()?;
+        Ok(Statement::Const(pattern, value))
     }
 
     fn parse_mut_statement(&mut self) -> Result<Statement, Error> {
         self.expect_token(Token::Mut)?;
-        if let Token::Identifier(identifier) = self.current_token().to_owned() {
-            self.advance_position();
-            self.expect_token(Token::Assign)?;
-            let value = self.parse_expression()?;
-            Ok(Statement::Mut(identifier, value))
-        } else {
-            Err(Error::Parser(format!(
-                "Unexpected token '{}', expected IDENTIFIER",
-                self.current_token(),
-            )))
+        let mut target = Expression::Identifier(self.parse_identifier()?);
+        loop {
+            if Token::LeftBracket.eq(self.current_token()) {
+                self.advance_position();
+                let index = self.parse_expression()?;
+                self.expect_token(Token::RightBracket)?;
+                target = Expression::Index(Box::new(target), Box::new(index));
+            } else if Token::Dot.eq(self.current_token()) {
+                self.advance_position();
+                target = Expression::Member(Box::new(target), self.parse_identifier()?);
+            } else {
+                break;
+            }
         }
+        self.expect_token(Token::Assign)?;
+        let value = self.parse_expression()?;
+        Ok(Statement::Mut(target, value))
+    }
+
+    fn parse_struct_statement(&mut self) -> Result<Statement, Error> {
+        self.expect_token(Token::Struct)?;
+        let name = self.parse_identifier()?;
+        self.expect_token(Token::LeftBrace)?;
+        let mut fields: Vec<String> = vec![];
+        while Token::ne(self.current_token(), &Token::RightBrace) {
+            let field = self.parse_identifier()?;
+            if fields.contains(&field) {
+                return Err(Error::Parser(format!(
+                    "Duplicate field '{field}' in struct '{name}'"
+                )));
+            }
+            fields.push(field);
+            if Token::eq(self.current_token(), &Token::Comma) {
+                self.advance_position();
+            } else {
+                break;
+            }
+        }
+        self.expect_token(Token::RightBrace)?;
+        Ok(Statement::Struct(name, fields))
+    }
+
+    fn parse_enum_statement(&mut self) -> Result<Statement, Error> {
+        self.expect_token(Token::Enum)?;
+        let name = self.parse_identifier()?;
+        self.expect_token(Token::LeftBrace)?;
+        let mut variants: Vec<(String, Vec<String>)> = vec![];
+        while Token::ne(self.current_token(), &Token::RightBrace) {
+            let variant = self.parse_identifier()?;
+            if variants.iter().any(|(name, _)| *name == variant) {
+                return Err(Error::Parser(format!(
+                    "Duplicate variant '{variant}' in enum '{name}'"
+                )));
+            }
+            let mut fields = vec![];
+            if Token::LeftParen.eq(self.current_token()) {
+                self.advance_position();
+                while Token::ne(self.current_token(), &Token::RightParen) {
+                    fields.push(self.parse_identifier()?);
+                    if Token::eq(self.current_token(), &Token::Comma) {
+                        self.advance_position();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(Token::RightParen)?;
+            }
+            variants.push((variant, fields));
+            if Token::eq(self.current_token(), &Token::Comma) {
+                self.advance_position();
+            } else {
+                break;
+            }
+        }
+        self.expect_token(Token::RightBrace)?;
+        Ok(Statement::Enum(name, variants))
     }
 
     fn parse_return_statement(&mut self) -> Result<Statement, Error> {
@@ -70,6 +142,28 @@ impl Parser {
         Ok(Statement::Return(value))
     }
 
+    fn parse_throw_statement(&mut self) -> Result<Statement, Error> {
+        self.expect_token(Token::Throw)?;
+        let value = self.parse_expression()?;
+        Ok(Statement::Throw(value))
+    }
+
+    fn parse_defer_statement(&mut self) -> Result<Statement, Error> {
+        self.expect_token(Token::Defer)?;
+        let value = self.parse_expression()?;
+        Ok(Statement::Defer(value))
+    }
+
+    fn parse_yield_statement(&mut self) -> Result<Statement, Error> {
+        self.expect_token(Token::Yield)?;
+        let delegate = Token::Ellipsis.eq(self.current_token());
+        if delegate {
+            self.advance_position();
+        }
+        let value = self.parse_expression()?;
+        Ok(Statement::Yield(value, delegate))
+    }
+
     fn parse_expression_statement(&mut self) -> Result<Statement, Error> {
         let value = self.parse_expression()?;
         Ok(Statement::Expression(value))
@@ -134,28 +228,50 @@ impl Parser {
     }
 
     fn parse_comparison_expression(&mut self) -> Result<Expression, Error> {
-        let mut left = self.parse_spread_expression()?;
-        while self.current_token_in(&[
-            Token::Lesser,
-            Token::LesserOrEqual,
-            Token::Greater,
-            Token::GreaterOrEqual,
-        ]) {
-            let operator = self.next_token();
-            let right = self.parse_spread_expression()?;
-            left = Expression::Infix(Box::new(left), operator, Box::new(right));
+        let mut left = self.parse_range_expression()?;
+        loop {
+            if self.current_token_in(&[
+                Token::Lesser,
+                Token::LesserOrEqual,
+                Token::Greater,
+                Token::GreaterOrEqual,
+                Token::In,
+            ]) {
+                let operator = self.next_token();
+                let right = self.parse_range_expression()?;
+                left = Expression::Infix(Box::new(left), operator, Box::new(right));
+            } else if Token::Not.eq(self.current_token()) && Token::In.eq(self.peek(1)) {
+                self.advance_position();
+                let operator = self.next_token();
+                let right = self.parse_range_expression()?;
+                left = Expression::Prefix(
+                    Token::Bang,
+                    Box::new(Expression::Infix(Box::new(left), operator, Box::new(right))),
+                );
+            } else {
+                return Ok(left);
+            }
         }
-        Ok(left)
     }
 
-    fn parse_spread_expression(&mut self) -> Result<Expression, Error> {
-        let mut left = self.parse_term_expression()?;
-        while Token::eq(self.current_token(), &Token::Spread) {
-            let operator = self.next_token();
-            let right = self.parse_term_expression()?;
-            left = Expression::Infix(Box::new(left), operator, Box::new(right));
+    fn parse_range_expression(&mut self) -> Result<Expression, Error> {
+        let start = self.parse_term_expression()?;
+        if !self.current_token_in(&[Token::Spread, Token::SpreadInclusive]) {
+            return Ok(start);
         }
-        Ok(left)
+        let inclusive = Token::SpreadInclusive.eq(&self.next_token());
+        let end = self.parse_term_expression()?;
+        let mut step = None;
+        if Token::Step.eq(self.current_token()) {
+            self.advance_position();
+            step = Some(Box::new(self.parse_term_expression()?));
+        }
+        Ok(Expression::Range(
+            Box::new(start),
+            Box::new(end),
+            step,
+            inclusive,
+        ))
     }
 
     fn parse_term_expression(&mut self) -> Result<Expression, Error> {
@@ -170,7 +286,7 @@ impl Parser {
 
     fn parse_factor_expression(&mut self) -> Result<Expression, Error> {
         let mut left = self.parse_prefix_expression()?;
-        while self.current_token_in(&[Token::Asterisk, Token::Slash]) {
+        while self.current_token_in(&[Token::Asterisk, Token::Slash, Token::Percent]) {
             let operator = self.next_token();
             let right = self.parse_prefix_expression()?;
             left = Expression::Infix(Box::new(left), operator, Box::new(right));
@@ -184,43 +300,75 @@ impl Parser {
             let right = self.parse_prefix_expression()?;
             Ok(Expression::Prefix(operator, Box::new(right)))
         } else {
-            self.parse_call_expression()
+            self.parse_postfix_expression()
         }
     }
 
-    fn parse_call_expression(&mut self) -> Result<Expression, Error> {
-        let expression = self.parse_index_expression()?;
-        match self.current_token() {
-            Token::LeftParen => {
-                self.advance_position();
-                let mut arguments = vec![];
-                if Token::ne(self.current_token(), &Token::RightParen) {
-                    loop {
-                        arguments.push(self.parse_expression()?);
-                        if Token::eq(self.current_token(), &Token::Comma) {
-                            self.advance_position();
-                            continue;
-                        } else {
-                            break;
+    fn parse_postfix_expression(&mut self) -> Result<Expression, Error> {
+        let mut expression = self.parse_literal_expression()?;
+        loop {
+            match self.current_token() {
+                Token::LeftParen => {
+                    self.advance_position();
+                    let mut arguments = vec![];
+                    if Token::ne(self.current_token(), &Token::RightParen) {
+                        loop {
+                            arguments.push(self.parse_argument()?);
+                            if Token::eq(self.current_token(), &Token::Comma) {
+                                self.advance_position();
+                                continue;
+                            } else {
+                                break;
+                            }
                         }
                     }
+                    self.expect_token(Token::RightParen)?;
+                    expression = Expression::Call(Box::new(expression), arguments);
                 }
-                self.expect_token(Token::RightParen)?;
-                Ok(Expression::Call(Box::new(expression), arguments))
+                Token::LeftBracket => {
+                    self.advance_position();
+                    let index = self.parse_expression()?;
+                    if Token::eq(self.current_token(), &Token::Colon) {
+                        self.advance_position();
+                        let step = self.parse_expression()?;
+                        self.expect_token(Token::RightBracket)?;
+                        expression = Expression::Slice(
+                            Box::new(expression),
+                            Box::new(index),
+                            Box::new(step),
+                        );
+                    } else {
+                        self.expect_token(Token::RightBracket)?;
+                        expression = Expression::Index(Box::new(expression), Box::new(index));
+                    }
+                }
+                Token::Dot => {
+                    self.advance_position();
+                    let name = self.parse_identifier()?;
+                    expression = Expression::Member(Box::new(expression), name);
+                }
+                Token::Question => {
+                    self.advance_position();
+                    expression = Expression::Propagate(Box::new(expression));
+                }
+                _ => return Ok(expression),
             }
-            _ => Ok(expression),
         }
     }
 
-    fn parse_index_expression(&mut self) -> Result<Expression, Error> {
-        let mut expression = self.parse_literal_expression()?;
-        while Token::eq(&Token::LeftBracket, self.current_token()) {
+    fn parse_argument(&mut self) -> Result<Argument, Error> {
+        if Token::eq(self.current_token(), &Token::Ellipsis) {
+            self.advance_position();
+            Ok(Argument::Spread(self.parse_expression()?))
+        } else if let (Token::Identifier(name), Token::Colon) = (self.current_token(), self.peek(1))
+        {
+            let name = name.to_owned();
             self.advance_position();
-            let index = self.parse_expression()?;
-            self.expect_token(Token::RightBracket)?;
-            expression = Expression::Index(Box::new(expression), Box::new(index));
+            self.advance_position();
+            Ok(Argument::Named(name, self.parse_expression()?))
+        } else {
+            Ok(Argument::Positional(self.parse_expression()?))
         }
-        Ok(expression)
     }
 
     fn parse_literal_expression(&mut self) -> Result<Expression, Error> {
@@ -235,14 +383,11 @@ impl Parser {
                 let mut parameters = vec![];
                 if Token::ne(self.current_token(), &Token::Pipe) {
                     loop {
-                        if let Token::Identifier(identifier) = self.current_token().to_owned() {
-                            self.advance_position();
-                            parameters.push(identifier)
-                        } else {
-                            return Err(Error::Parser(format!(
-                                "Unexpected token '{}', expected IDENTIFIER",
-                                self.current_token(),
-                            )));
+                        let parameter = self.parse_parameter()?;
+                        let rest = parameter.rest;
+                        parameters.push(parameter);
+                        if rest {
+                            break;
                         }
                         if Token::eq(self.current_token(), &Token::Comma) {
                             self.advance_position();
@@ -256,6 +401,60 @@ impl Parser {
                 let body = self.parse_expression()?;
                 Ok(Expression::Function(parameters, Box::new(body)))
             }
+            Token::Try => {
+                self.advance_position();
+                let body = self.parse_block_expression()?;
+                let mut catch = None;
+                if Token::Catch.eq(self.current_token()) {
+                    self.advance_position();
+                    let mut name = None;
+                    if let Token::Identifier(identifier) = self.current_token().to_owned() {
+                        self.advance_position();
+                        name = Some(identifier);
+                    }
+                    catch = Some((name, Box::new(self.parse_block_expression()?)));
+                }
+                let mut finally = None;
+                if Token::Finally.eq(self.current_token()) {
+                    self.advance_position();
+                    finally = Some(Box::new(self.parse_block_expression()?));
+                }
+                if catch.is_none() && finally.is_none() {
+                    return Err(Error::Parser(format!(
+                        "Unexpected token '{}', expected 'catch' or 'finally'",
+                        self.current_token()
+                    )));
+                }
+                Ok(Expression::Try(Box::new(body), catch, finally))
+            }
+            Token::Match => {
+                self.advance_position();
+                let value = self.parse_expression()?;
+                self.expect_token(Token::LeftBrace)?;
+                let mut arms = vec![];
+                while Token::ne(self.current_token(), &Token::RightBrace) {
+                    let pattern = self.parse_pattern()?;
+                    let mut guard = None;
+                    if Token::If.eq(self.current_token()) {
+                        self.advance_position();
+                        guard = Some(self.parse_expression()?);
+                    }
+                    self.expect_token(Token::FatArrow)?;
+                    let body = self.parse_expression()?;
+                    arms.push(MatchArm {
+                        pattern,
+                        guard,
+                        body,
+                    });
+                    if Token::eq(self.current_token(), &Token::Comma) {
+                        self.advance_position();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(Token::RightBrace)?;
+                Ok(Expression::Match(Box::new(value), arms))
+            }
             Token::If => {
                 self.advance_position();
                 let condition = self.parse_expression()?;
@@ -268,30 +467,25 @@ impl Parser {
                 Ok(Expression::If(
                     Box::new(condition),
                     Box::new(consequence),
-                    alternative.map(|expression| Box::new(expression)),
+                    alternative.map(Box::new),
                 ))
             }
-            Token::LeftBrace => {
-                let mut statements = vec![];
-                self.expect_token(Token::LeftBrace)?;
-                while Token::RightBrace.ne(self.current_token())
-                    && Token::Eof.ne(self.current_token())
-                {
-                    statements.push(self.parse_statement()?);
-                }
-                self.expect_token(Token::RightBrace)?;
-                Ok(Expression::Block(statements))
-            }
+            Token::LeftBrace => self.parse_block_expression(),
             Token::Hash => {
-                let mut pairs = vec![];
+                let mut entries = vec![];
                 self.advance_position();
                 self.expect_token(Token::LeftBracket)?;
                 if Token::ne(self.current_token(), &Token::RightBracket) {
                     loop {
-                        let key = self.parse_expression()?;
-                        self.expect_token(Token::Colon)?;
-                        let value = self.parse_expression()?;
-                        pairs.push((key, value));
+                        if Token::eq(self.current_token(), &Token::Ellipsis) {
+                            self.advance_position();
+                            entries.push(Entry::Spread(self.parse_expression()?));
+                        } else {
+                            let key = self.parse_expression()?;
+                            self.expect_token(Token::Colon)?;
+                            let value = self.parse_expression()?;
+                            entries.push(Entry::Pair(key, value));
+                        }
                         if Token::eq(self.current_token(), &Token::Comma) {
                             self.advance_position();
                             continue;
@@ -301,15 +495,20 @@ impl Parser {
                     }
                 }
                 self.expect_token(Token::RightBracket)?;
-                Ok(Expression::Hash(pairs))
+                Ok(Expression::Hash(entries))
             }
             Token::Array => {
-                let mut expressions = vec![];
+                let mut elements = vec![];
                 self.advance_position();
                 self.expect_token(Token::LeftBracket)?;
                 if Token::ne(self.current_token(), &Token::RightBracket) {
                     loop {
-                        expressions.push(self.parse_expression()?);
+                        if Token::eq(self.current_token(), &Token::Ellipsis) {
+                            self.advance_position();
+                            elements.push(Element::Spread(self.parse_expression()?));
+                        } else {
+                            elements.push(Element::Value(self.parse_expression()?));
+                        }
                         if Token::eq(self.current_token(), &Token::Comma) {
                             self.advance_position();
                             continue;
@@ -319,7 +518,7 @@ impl Parser {
                     }
                 }
                 self.expect_token(Token::RightBracket)?;
-                Ok(Expression::Array(expressions))
+                Ok(Expression::Array(elements))
             }
             Token::LeftParen => {
                 self.advance_position();
@@ -337,12 +536,19 @@ impl Parser {
             }
             Token::Integer(integer) => {
                 self.advance_position();
-                Ok(Expression::Integer(integer.parse().unwrap()))
+                match integer.parse() {
+                    Ok(integer) => Ok(Expression::Integer(integer)),
+                    Err(_) => Ok(Expression::BigInteger(integer.parse().unwrap())),
+                }
             }
             Token::Float(float) => {
                 self.advance_position();
                 Ok(Expression::Float(float.parse().unwrap()))
             }
+            Token::Decimal(decimal) => {
+                self.advance_position();
+                Ok(Expression::Decimal(decimal.parse().unwrap()))
+            }
             Token::String(string) => {
                 self.advance_position();
                 Ok(Expression::String(string))
@@ -361,6 +567,141 @@ impl Parser {
         }
     }
 
+    fn parse_block_expression(&mut self) -> Result<Expression, Error> {
+        let mut statements = vec![];
+        self.expect_token(Token::LeftBrace)?;
+        while Token::RightBrace.ne(self.current_token()) && Token::Eof.ne(self.current_token()) {
+            statements.push(self.parse_statement()?);
+        }
+        self.expect_token(Token::RightBrace)?;
+        Ok(Expression::Block(statements))
+    }
+
+    fn parse_parameter(&mut self) -> Result<Parameter, Error> {
+        let rest = Token::Ellipsis.eq(self.current_token());
+        if rest {
+            self.advance_position();
+        }
+        let pattern = self.parse_pattern()?;
+        let mut default = None;
+        if !rest && Token::Assign.eq(self.current_token()) {
+            self.advance_position();
+            default = Some(self.parse_bitwise_and_expression()?);
+        }
+        Ok(Parameter {
+            pattern,
+            default,
+            rest,
+        })
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, Error> {
+        match self.current_token().to_owned() {
+            Token::Identifier(identifier) if identifier == "_" => {
+                self.advance_position();
+                Ok(Pattern::Wildcard)
+            }
+            Token::Identifier(identifier) if Token::LeftParen.eq(self.peek(1)) => {
+                self.advance_position();
+                self.advance_position();
+                let mut patterns = vec![];
+                while Token::ne(self.current_token(), &Token::RightParen) {
+                    patterns.push(self.parse_pattern()?);
+                    if Token::eq(self.current_token(), &Token::Comma) {
+                        self.advance_position();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(Token::RightParen)?;
+                Ok(Pattern::Variant(identifier, patterns))
+            }
+            Token::Identifier(identifier) => {
+                self.advance_position();
+                Ok(Pattern::Identifier(identifier))
+            }
+            Token::Integer(_)
+            | Token::Float(_)
+            | Token::Decimal(_)
+            | Token::String(_)
+            | Token::True
+            | Token::False
+            | Token::Minus => {
+                let start = self.parse_prefix_expression()?;
+                match self.current_token() {
+                    Token::Spread | Token::SpreadInclusive => {
+                        let inclusive = Token::SpreadInclusive.eq(self.current_token());
+                        self.advance_position();
+                        let end = self.parse_prefix_expression()?;
+                        Ok(Pattern::Range(start, end, inclusive))
+                    }
+                    _ => Ok(Pattern::Literal(start)),
+                }
+            }
+            Token::LeftBracket => {
+                self.advance_position();
+                let mut patterns = vec![];
+                let mut rest = None;
+                while Token::ne(self.current_token(), &Token::RightBracket) {
+                    if Token::eq(self.current_token(), &Token::Ellipsis) {
+                        self.advance_position();
+                        rest = Some(self.parse_identifier()?);
+                        break;
+                    }
+                    patterns.push(self.parse_pattern()?);
+                    if Token::eq(self.current_token(), &Token::Comma) {
+                        self.advance_position();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(Token::RightBracket)?;
+                Ok(Pattern::Array(patterns, rest))
+            }
+            Token::LeftBrace => {
+                self.advance_position();
+                let mut entries = vec![];
+                let mut rest = None;
+                while Token::ne(self.current_token(), &Token::RightBrace) {
+                    if Token::eq(self.current_token(), &Token::Ellipsis) {
+                        self.advance_position();
+                        rest = Some(self.parse_identifier()?);
+                        break;
+                    }
+                    let key = self.parse_identifier()?;
+                    if Token::eq(self.current_token(), &Token::Colon) {
+                        self.advance_position();
+                        entries.push((key, self.parse_pattern()?));
+                    } else {
+                        entries.push((key.to_owned(), Pattern::Identifier(key)));
+                    }
+                    if Token::eq(self.current_token(), &Token::Comma) {
+                        self.advance_position();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(Token::RightBrace)?;
+                Ok(Pattern::Hash(entries, rest))
+            }
+            token => Err(Error::Parser(format!(
+                "Unexpected token '{token}', expected PATTERN"
+            ))),
+        }
+    }
+
+    fn parse_identifier(&mut self) -> Result<String, Error> {
+        if let Token::Identifier(identifier) = self.current_token().to_owned() {
+            self.advance_position();
+            Ok(identifier)
+        } else {
+            Err(Error::Parser(format!(
+                "Unexpected token '{}', expected IDENTIFIER",
+                self.current_token(),
+            )))
+        }
+    }
+
     fn peek(&self, offset: usize) -> &Token {
         let index = self.position + offset;
         if index < self.tokens.len() {
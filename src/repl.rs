@@ -31,7 +31,7 @@ pub fn run() {
             Program::default()
         });
         match evaluate(&program, Rc::clone(&environment)) {
-            Ok(object) => println!("{object}"),
+            Ok(object) => println!("{object:#}"),
             Err(error) => error.report(),
         };
     }
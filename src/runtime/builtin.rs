@@ -1,9 +1,25 @@
-use crate::common::{error::Error, object::Object};
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
 
-use super::environment::Environment;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::common::{
+    array::Array,
+    bigint::BigInt,
+    decimal::{self, Rounding},
+    error::Error,
+    object::{ErrorValue, Object},
+    ordered_hash::OrderedHash,
+};
+
+use super::{
+    environment::Environment,
+    evaluator::{spread, stringify},
+    generator,
+    iterator::{self, Iter},
+};
 
 fn exit(objects: Vec<Object>) -> Result<Object, Error> {
-    let exit_code = match objects.get(0) {
+    let exit_code = match objects.first() {
         Some(Object::Integer(exit_code)) => *exit_code as i32,
         _ => 0,
     };
@@ -12,65 +28,532 @@ fn exit(objects: Vec<Object>) -> Result<Object, Error> {
 
 fn write(objects: Vec<Object>) -> Result<Object, Error> {
     for object in objects {
-        print!("{object}");
+        print!("{}", stringify(&object)?);
     }
     println!();
     Ok(Object::Null)
 }
 
+fn repr(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.first() {
+        Some(object) => Object::String(object.repr()),
+        None => invalid_argument("repr", None),
+    };
+    Ok(object)
+}
+
+fn to_string(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.first() {
+        Some(object) => Object::String(stringify(object)?),
+        None => invalid_argument("to_string", None),
+    };
+    Ok(object)
+}
+
 fn len(objects: Vec<Object>) -> Result<Object, Error> {
-    let object = match objects.get(0) {
+    let object = match objects.first() {
         Some(Object::Array(array)) => Object::Integer(array.len() as i64),
-        Some(Object::String(string)) => Object::Integer(string.len() as i64),
-        _ => Object::Null,
+        Some(Object::Range(range)) => Object::from(BigInt::from_u128(range.len())),
+        Some(Object::String(string)) => Object::Integer(string.chars().count() as i64),
+        object => invalid_argument("len", object),
+    };
+    Ok(object)
+}
+
+fn chars(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.first() {
+        Some(Object::String(string)) => Object::Array(
+            string
+                .chars()
+                .map(|character| Object::String(character.to_string()))
+                .collect(),
+        ),
+        object => invalid_argument("chars", object),
+    };
+    Ok(object)
+}
+
+fn bytes(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.first() {
+        Some(Object::String(string)) => Object::Array(
+            string
+                .bytes()
+                .map(|byte| Object::Integer(byte as i64))
+                .collect(),
+        ),
+        object => invalid_argument("bytes", object),
+    };
+    Ok(object)
+}
+
+fn graphemes(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.first() {
+        Some(Object::String(string)) => Object::Array(
+            string
+                .graphemes(true)
+                .map(|grapheme| Object::String(grapheme.to_owned()))
+                .collect(),
+        ),
+        object => invalid_argument("graphemes", object),
     };
     Ok(object)
 }
 
 fn first(objects: Vec<Object>) -> Result<Object, Error> {
-    let object = match objects.get(0) {
-        Some(Object::Array(array)) => array.get(0).unwrap_or(&Object::Null),
-        _ => &Object::Null,
+    let object = match objects.first() {
+        Some(Object::Array(array)) => array.first().cloned().unwrap_or(Object::Null),
+        object => invalid_argument("first", object),
     };
-    Ok(object.to_owned())
+    Ok(object)
 }
 
 fn rest(objects: Vec<Object>) -> Result<Object, Error> {
-    let object = match objects.get(0) {
-        Some(Object::Array(array)) => match (array.get(1), array.get(array.len() - 1)) {
-            (Some(_), Some(_)) => Object::Array(array[1..array.len()].to_vec()),
+    let object = match objects.first() {
+        Some(Object::Array(array)) => match (array.get(1), array.last()) {
+            (Some(_), Some(_)) => Object::Array(array[1..array.len()].to_vec().into()),
             (_, _) => Object::Null,
         },
-        _ => Object::Null,
+        object => invalid_argument("rest", object),
     };
     Ok(object)
 }
 
 fn append(objects: Vec<Object>) -> Result<Object, Error> {
-    let object = match objects.get(0) {
+    let object = match objects.first() {
         Some(Object::Array(array)) => {
-            if let Some(_) = objects.get(1) {
-                let mut array = array.to_owned();
+            if objects.get(1).is_some() {
+                let mut array = array.to_vec();
                 for object in &objects[1..objects.len()] {
                     array.push(object.to_owned());
                 }
                 array
             } else {
-                array.to_owned()
+                array.to_vec()
             }
         }
-        _ => vec![],
+        object => return Ok(invalid_argument("append", object)),
+    };
+    Ok(Object::Array(object.into()))
+}
+
+fn keys(objects: Vec<Object>) -> Result<Object, Error> {
+    match objects.first() {
+        Some(Object::Hash(pairs)) => Ok(Object::Array(pairs.keys().cloned().collect())),
+        Some(Object::Record(kind, _)) => Ok(Object::Array(
+            kind.fields
+                .iter()
+                .map(|field| Object::String(field.to_owned()))
+                .collect(),
+        )),
+        object => Ok(invalid_argument("keys", object)),
+    }
+}
+
+fn values(objects: Vec<Object>) -> Result<Object, Error> {
+    match objects.first() {
+        Some(Object::Hash(pairs)) => Ok(Object::Array(pairs.values().cloned().collect())),
+        Some(Object::Record(_, values)) => Ok(Object::Array(values.to_owned().into())),
+        object => Ok(invalid_argument("values", object)),
+    }
+}
+
+fn next(objects: Vec<Object>) -> Result<Object, Error> {
+    let (value, default, kind) = match objects.as_slice() {
+        [Object::Generator(generator), rest @ ..] if rest.len() <= 1 => {
+            (generator::resume(generator)?, rest.first(), "Generator")
+        }
+        [Object::Iterator(iterator), rest @ ..] if rest.len() <= 1 => {
+            (iterator::next(iterator)?, rest.first(), "Iterator")
+        }
+        _ => return Ok(invalid_argument("next", objects.first())),
+    };
+    match (value, default) {
+        (Some(value), _) => Ok(value),
+        (None, Some(default)) => Ok(default.to_owned()),
+        (None, None) => Ok(exhausted(kind)),
+    }
+}
+
+fn stop_iteration(objects: Vec<Object>) -> Result<Object, Error> {
+    match objects.as_slice() {
+        [] => Ok(exhausted("Iterator")),
+        _ => Ok(invalid_argument("stop_iteration", objects.first())),
+    }
+}
+
+fn exhausted(kind: &str) -> Object {
+    Object::Error(Rc::new(ErrorValue::new(
+        "StopIteration",
+        format!("{kind} is exhausted"),
+        Object::Null,
+    )))
+}
+
+fn lazy(iterator: Result<Iter, Object>) -> Object {
+    match iterator {
+        Ok(iterator) => Object::Iterator(Rc::new(RefCell::new(iterator))),
+        Err(error) => error,
+    }
+}
+
+fn source(name: &str, object: &Object) -> Result<Box<Iter>, Object> {
+    match Iter::new(object.to_owned()) {
+        Ok(iterator) => Ok(Box::new(iterator)),
+        Err(_) => Err(invalid_argument(name, Some(object))),
+    }
+}
+
+fn count(name: &str, object: &Object) -> Result<usize, Object> {
+    match object {
+        Object::Integer(count) if *count >= 0 => Ok(*count as usize),
+        object => Err(invalid_argument(name, Some(object))),
+    }
+}
+
+fn iter(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [Object::Iterator(_)] => objects[0].to_owned(),
+        [object] => lazy(source("iter", object).map(|source| *source)),
+        _ => invalid_argument("iter", objects.get(1)),
+    };
+    Ok(object)
+}
+
+fn map(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object, function] => {
+            lazy(source("map", object).map(|source| Iter::Map(source, function.to_owned())))
+        }
+        _ => invalid_argument("map", objects.get(2)),
+    };
+    Ok(object)
+}
+
+fn filter(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object, predicate] => {
+            lazy(source("filter", object).map(|source| Iter::Filter(source, predicate.to_owned())))
+        }
+        _ => invalid_argument("filter", objects.get(2)),
+    };
+    Ok(object)
+}
+
+fn take(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object, limit] => lazy(
+            source("take", object).and_then(|source| Ok(Iter::Take(source, count("take", limit)?))),
+        ),
+        _ => invalid_argument("take", objects.get(2)),
+    };
+    Ok(object)
+}
+
+fn skip(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object, limit] => lazy(
+            source("skip", object).and_then(|source| Ok(Iter::Skip(source, count("skip", limit)?))),
+        ),
+        _ => invalid_argument("skip", objects.get(2)),
+    };
+    Ok(object)
+}
+
+fn zip(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [left, right] => {
+            lazy(source("zip", left).and_then(|left| Ok(Iter::Zip(left, source("zip", right)?))))
+        }
+        _ => invalid_argument("zip", objects.get(2)),
     };
-    Ok(Object::Array(object))
+    Ok(object)
+}
+
+fn enumerate(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object] => lazy(source("enumerate", object).map(|source| Iter::Enumerate(source, 0))),
+        _ => invalid_argument("enumerate", objects.get(1)),
+    };
+    Ok(object)
+}
+
+fn chain(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [first, second] => lazy(
+            source("chain", first)
+                .and_then(|first| Ok(Iter::Chain(first, source("chain", second)?))),
+        ),
+        _ => invalid_argument("chain", objects.get(2)),
+    };
+    Ok(object)
+}
+
+fn flat_map(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object, function] => lazy(
+            source("flat_map", object)
+                .map(|source| Iter::FlatMap(source, function.to_owned(), None)),
+        ),
+        _ => invalid_argument("flat_map", objects.get(2)),
+    };
+    Ok(object)
+}
+
+fn collect(objects: Vec<Object>) -> Result<Object, Error> {
+    let object = match objects.as_slice() {
+        [object] => match source("collect", object) {
+            Ok(source) => Object::Array(source.collect()?.into()),
+            Err(error) => error,
+        },
+        _ => invalid_argument("collect", objects.get(1)),
+    };
+    Ok(object)
+}
+
+fn sort(mut objects: Vec<Object>) -> Result<Object, Error> {
+    if let Some(Object::Generator(_) | Object::Iterator(_)) = objects.first() {
+        let values = spread(objects.remove(0))?;
+        objects.insert(0, Object::Array(values.into()));
+    }
+    match objects.first() {
+        Some(Object::Array(array)) => Ok(Object::Array(merge_sort(array.to_vec())?.into())),
+        Some(object) => Err(Error::Runtime(format!("Can not sort '{object}'"))),
+        None => Err(Error::Runtime("Expected 1 argument(s), got 0".to_string())),
+    }
+}
+
+/// A stable merge sort that stops at the first pair of values that can not be compared.
+/// `compare` is not a total order across mixed kinds, which `sort_by` requires.
+fn merge_sort(mut values: Vec<Object>) -> Result<Vec<Object>, Error> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+    let right = merge_sort(values.split_off(values.len() / 2))?;
+    let left = merge_sort(values)?;
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let (mut left, mut right) = (left.into_iter().peekable(), right.into_iter().peekable());
+    while let (Some(x), Some(y)) = (left.peek(), right.peek()) {
+        let next = match y.compare(x)? {
+            Ordering::Less => right.next(),
+            _ => left.next(),
+        };
+        merged.extend(next);
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+fn min(objects: Vec<Object>) -> Result<Object, Error> {
+    extremum(objects, Ordering::Less)
+}
+
+fn max(objects: Vec<Object>) -> Result<Object, Error> {
+    extremum(objects, Ordering::Greater)
+}
+
+fn extremum(mut objects: Vec<Object>, wanted: Ordering) -> Result<Object, Error> {
+    let values = match objects.as_slice() {
+        [Object::Array(array)] => array.to_vec(),
+        [Object::Generator(_) | Object::Iterator(_)] => spread(objects.remove(0))?,
+        _ => objects,
+    };
+    let mut values = values.into_iter();
+    let mut extremum = values.next().ok_or(Error::Runtime(
+        "Expected at least 1 value, got 0".to_string(),
+    ))?;
+    for value in values {
+        if value.compare(&extremum)? == wanted {
+            extremum = value;
+        }
+    }
+    Ok(extremum)
+}
+
+fn decimal(objects: Vec<Object>) -> Result<Object, Error> {
+    let value = match objects.first() {
+        Some(Object::String(string)) => string.trim().parse().ok(),
+        Some(Object::Float(float)) if float.is_finite() => float.to_string().parse().ok(),
+        Some(object) => object.to_decimal(),
+        None => None,
+    };
+    match (value, objects.first()) {
+        (Some(value), _) => Ok(Object::Decimal(value)),
+        (None, Some(object)) => Err(Error::Runtime(format!(
+            "Can not convert '{object}' to decimal"
+        ))),
+        (None, None) => Err(Error::Runtime("Expected 1 argument(s), got 0".to_string())),
+    }
+}
+
+fn float(objects: Vec<Object>) -> Result<Object, Error> {
+    let value = match objects.first() {
+        Some(Object::Float(float)) => Some(*float),
+        Some(Object::Integer(integer)) => Some(*integer as f64),
+        Some(Object::BigInteger(integer)) => integer.to_string().parse().ok(),
+        Some(Object::Decimal(decimal)) => Some(decimal.to_f64()),
+        Some(Object::String(string)) => string.trim().parse().ok(),
+        _ => None,
+    };
+    match (value, objects.first()) {
+        (Some(value), _) => Ok(Object::Float(value)),
+        (None, Some(object)) => Err(Error::Runtime(format!(
+            "Can not convert '{object}' to float"
+        ))),
+        (None, None) => Err(Error::Runtime("Expected 1 argument(s), got 0".to_string())),
+    }
+}
+
+fn round(objects: Vec<Object>) -> Result<Object, Error> {
+    let scale = match objects.get(1) {
+        Some(Object::Integer(scale)) if *scale >= 0 => *scale as u32,
+        Some(object) => return Err(Error::Runtime(format!("Scale '{object}' not valid"))),
+        None => 0,
+    };
+    let rounding = match objects.get(2) {
+        Some(object) => rounding_mode(object)?,
+        None => decimal::context().rounding,
+    };
+    match objects.first() {
+        Some(Object::Decimal(value)) => Ok(Object::Decimal(value.rescale(scale, rounding))),
+        Some(Object::Float(value)) => {
+            let factor = 10f64.powi(scale as i32);
+            Ok(Object::Float((value * factor).round() / factor))
+        }
+        Some(object @ (Object::Integer(_) | Object::BigInteger(_))) => Ok(object.to_owned()),
+        Some(object) => Err(Error::Runtime(format!("Can not round '{object}'"))),
+        None => Err(Error::Runtime("Expected 1 argument(s), got 0".to_string())),
+    }
+}
+
+fn decimal_context(objects: Vec<Object>) -> Result<Object, Error> {
+    let mut context = decimal::context();
+    match objects.first() {
+        Some(Object::Integer(scale)) if *scale >= 0 => context.scale = *scale as u32,
+        Some(object) => return Err(Error::Runtime(format!("Scale '{object}' not valid"))),
+        None => {}
+    }
+    if let Some(object) = objects.get(1) {
+        context.rounding = rounding_mode(object)?;
+    }
+    decimal::set_context(context);
+    Ok(Object::Null)
+}
+
+fn freeze(objects: Vec<Object>) -> Result<Object, Error> {
+    match objects.first() {
+        Some(object) => Ok(frozen(object)),
+        None => Err(Error::Runtime("Expected 1 argument(s), got 0".to_string())),
+    }
+}
+
+fn frozen(object: &Object) -> Object {
+    match object {
+        Object::Array(values) => {
+            let mut array: Array = values.iter().map(frozen).collect();
+            array.freeze();
+            Object::Array(array)
+        }
+        Object::Hash(pairs) => {
+            let mut hash = OrderedHash::new();
+            for (key, value) in pairs.iter() {
+                hash.insert(key.to_owned(), frozen(value));
+            }
+            hash.freeze();
+            Object::Hash(hash)
+        }
+        object => object.to_owned(),
+    }
+}
+
+fn error(objects: Vec<Object>) -> Result<Object, Error> {
+    let data = objects.get(1).cloned().unwrap_or(Object::Null);
+    match objects.first() {
+        Some(Object::String(message)) => Ok(Object::Error(Rc::new(ErrorValue::new(
+            "Error",
+            message.to_owned(),
+            data,
+        )))),
+        Some(object) => Err(Error::Runtime(format!(
+            "Error message '{object}' not valid"
+        ))),
+        None => Err(Error::Runtime(
+            "Expected 1 to 2 argument(s), got 0".to_string(),
+        )),
+    }
+}
+
+fn is_error(objects: Vec<Object>) -> Result<Object, Error> {
+    Ok(Object::Boolean(matches!(
+        objects.first(),
+        Some(Object::Error(_))
+    )))
+}
+
+fn invalid_argument(name: &str, object: Option<&Object>) -> Object {
+    let message = match object {
+        Some(object) => format!("Argument '{object}' not valid for '{name}'"),
+        None => format!("Missing argument for '{name}'"),
+    };
+    Object::Error(Rc::new(ErrorValue::new(
+        "ArgumentError",
+        message,
+        object.cloned().unwrap_or(Object::Null),
+    )))
+}
+
+fn rounding_mode(object: &Object) -> Result<Rounding, Error> {
+    match object {
+        Object::String(name) => name.parse().ok(),
+        _ => None,
+    }
+    .ok_or(Error::Runtime(format!(
+        "Rounding mode '{object}' not valid"
+    )))
 }
 
 pub(crate) fn get_builtin() -> Environment {
     let mut environment = Environment::new();
     environment.set("exit".to_string(), Object::Builtin(exit));
     environment.set("write".to_string(), Object::Builtin(write));
+    environment.set("repr".to_string(), Object::Builtin(repr));
+    environment.set("to_string".to_string(), Object::Builtin(to_string));
     environment.set("len".to_string(), Object::Builtin(len));
+    environment.set("freeze".to_string(), Object::Builtin(freeze));
+    environment.set("error".to_string(), Object::Builtin(error));
+    environment.set("is_error".to_string(), Object::Builtin(is_error));
+    environment.set("chars".to_string(), Object::Builtin(chars));
+    environment.set("bytes".to_string(), Object::Builtin(bytes));
+    environment.set("graphemes".to_string(), Object::Builtin(graphemes));
     environment.set("first".to_string(), Object::Builtin(first));
     environment.set("rest".to_string(), Object::Builtin(rest));
     environment.set("append".to_string(), Object::Builtin(append));
+    environment.set("keys".to_string(), Object::Builtin(keys));
+    environment.set("values".to_string(), Object::Builtin(values));
+    environment.set("next".to_string(), Object::Builtin(next));
+    environment.set(
+        "stop_iteration".to_string(),
+        Object::Builtin(stop_iteration),
+    );
+    environment.set("iter".to_string(), Object::Builtin(iter));
+    environment.set("map".to_string(), Object::Builtin(map));
+    environment.set("filter".to_string(), Object::Builtin(filter));
+    environment.set("take".to_string(), Object::Builtin(take));
+    environment.set("skip".to_string(), Object::Builtin(skip));
+    environment.set("zip".to_string(), Object::Builtin(zip));
+    environment.set("enumerate".to_string(), Object::Builtin(enumerate));
+    environment.set("chain".to_string(), Object::Builtin(chain));
+    environment.set("flat_map".to_string(), Object::Builtin(flat_map));
+    environment.set("collect".to_string(), Object::Builtin(collect));
+    environment.set("sort".to_string(), Object::Builtin(sort));
+    environment.set("min".to_string(), Object::Builtin(min));
+    environment.set("max".to_string(), Object::Builtin(max));
+    environment.set("decimal".to_string(), Object::Builtin(decimal));
+    environment.set("float".to_string(), Object::Builtin(float));
+    environment.set("round".to_string(), Object::Builtin(round));
+    environment.set(
+        "decimal_context".to_string(),
+        Object::Builtin(decimal_context),
+    );
     environment
 }
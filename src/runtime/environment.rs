@@ -1,10 +1,15 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
-use crate::common::object::Object;
+use crate::common::{error::Error, object::Object};
 
 #[derive(Debug)]
 pub(crate) struct Environment {
     bindings: HashMap<String, Object>,
+    constants: HashSet<String>,
     parent: Option<Rc<RefCell<Environment>>>,
 }
 
@@ -12,6 +17,7 @@ impl Environment {
     pub(crate) fn new() -> Environment {
         Environment {
             bindings: HashMap::new(),
+            constants: HashSet::new(),
             parent: None,
         }
     }
@@ -19,6 +25,7 @@ impl Environment {
     pub(crate) fn extend(parent: Rc<RefCell<Environment>>) -> Environment {
         Environment {
             bindings: HashMap::new(),
+            constants: HashSet::new(),
             parent: Some(parent),
         }
     }
@@ -37,16 +44,39 @@ impl Environment {
         self.bindings.insert(name, value);
     }
 
-    pub(crate) fn mutate(&mut self, name: &str, value: Object) -> Option<Object> {
+    pub(crate) fn declare(&mut self, name: &str, value: Object) -> Result<(), Error> {
+        if self.is_constant(name) {
+            return Err(Error::Runtime(format!(
+                "Can not redeclare constant '{name}'"
+            )));
+        }
+        self.set(name.to_owned(), value);
+        Ok(())
+    }
+
+    pub(crate) fn declare_constant(&mut self, name: &str) {
+        self.constants.insert(name.to_owned());
+    }
+
+    pub(crate) fn is_constant(&self, name: &str) -> bool {
+        self.constants.contains(name)
+    }
+
+    pub(crate) fn mutate(&mut self, name: &str, value: Object) -> Result<Object, Error> {
+        if self.is_constant(name) {
+            return Err(Error::Runtime(format!(
+                "Can not assign to constant '{name}'"
+            )));
+        }
         match self.bindings.get(name) {
             Some(_) => {
                 self.set(name.to_owned(), value.to_owned());
-                Some(value)
+                Ok(value)
             }
-            None => self
-                .parent
-                .as_deref()
-                .and_then(|environment| environment.borrow_mut().mutate(name, value)),
+            None => match self.parent.as_deref() {
+                Some(environment) => environment.borrow_mut().mutate(name, value),
+                None => Err(Error::Runtime(format!("Name '{name}' is not defined"))),
+            },
         }
     }
 }
@@ -1,50 +1,143 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{self, Write},
+    rc::Rc,
+};
 
 use crate::common::{
-    ast::{Expression, Program, Statement},
+    ast::{Argument, Element, Entry, Expression, MatchArm, Pattern, Program, Statement},
+    bigint::BigInt,
+    decimal::{self, Decimal},
     error::Error,
-    object::Object,
+    object::{Enum, ErrorValue, Function, Object, Struct, Variant},
+    ordered_hash::OrderedHash,
+    range::Range,
     token::Token,
 };
 
-use super::environment::Environment;
+use super::{environment::Environment, generator::Generator, iterator::Iter};
 
 pub(crate) fn evaluate(
     program: &Program,
     environment: Rc<RefCell<Environment>>,
 ) -> Result<Object, Error> {
-    let mut value = Object::Null;
-    for statement in &program.statements {
-        value = evaluate_statement(statement, Rc::clone(&environment))?;
-        if let Object::Return(value) = value {
-            return Ok(*value);
+    match evaluate_block(&program.statements, environment) {
+        Err(Error::Return(value)) => Ok(value),
+        result => result,
+    }
+}
+
+fn evaluate_block(
+    statements: &[Statement],
+    environment: Rc<RefCell<Environment>>,
+) -> Result<Object, Error> {
+    let mut deferred = vec![];
+    let mut result = Ok(Object::Null);
+    for statement in statements {
+        if let Statement::Defer(expression) = statement {
+            deferred.push(expression);
+            continue;
+        }
+        result = evaluate_statement(statement, Rc::clone(&environment));
+        if result.is_err() {
+            break;
         }
     }
-    Ok(value)
+    for expression in deferred.into_iter().rev() {
+        result = run_deferred(expression, Rc::clone(&environment), result);
+    }
+    result
 }
 
-fn evaluate_statement(
+/// Runs a deferred expression after a block exits with `result`. An error from the
+/// deferred expression replaces a normal exit, including `return` and `?`, but the
+/// first real error of a failed block is kept.
+pub(crate) fn run_deferred<T>(
+    expression: &Expression,
+    environment: Rc<RefCell<Environment>>,
+    result: Result<T, Error>,
+) -> Result<T, Error> {
+    match (result, evaluate_expression(expression, environment)) {
+        (Ok(_) | Err(Error::Return(_) | Error::Propagated(_)), Err(error)) => Err(error),
+        (result, _) => result,
+    }
+}
+
+pub(crate) fn evaluate_statement(
     statement: &Statement,
     environment: Rc<RefCell<Environment>>,
 ) -> Result<Object, Error> {
     match statement {
-        Statement::Let(name, expression) => {
+        Statement::Let(pattern, expression) => {
             let value = evaluate_expression(expression, Rc::clone(&environment))?;
-            environment
-                .borrow_mut()
-                .set(name.to_owned(), value.to_owned());
+            bind_pattern(pattern, value.to_owned(), &environment)?;
+            Ok(value)
+        }
+        Statement::Const(pattern, expression) => {
+            let value = evaluate_expression(expression, Rc::clone(&environment))?;
+            bind_pattern(pattern, value.to_owned(), &environment)?;
+            for name in pattern.names() {
+                environment.borrow_mut().declare_constant(name);
+            }
             Ok(value)
         }
-        Statement::Mut(name, expression) => {
+        Statement::Mut(target, expression) => {
             let value = evaluate_expression(expression, Rc::clone(&environment))?;
-            environment
-                .borrow_mut()
-                .mutate(&name, value)
-                .ok_or(Error::Runtime(format!("Name '{name}' is not defined")))
+            assign(target, value, &environment)
         }
         Statement::Return(expression) => {
             let value = evaluate_expression(expression, Rc::clone(&environment))?;
-            Ok(Object::Return(Box::new(value)))
+            Err(Error::Return(value))
+        }
+        Statement::Yield(..) => Err(Error::Runtime(
+            "Yield is only allowed inside a function".to_string(),
+        )),
+        Statement::Defer(_) => Err(Error::Runtime(
+            "Defer is only allowed inside a block".to_string(),
+        )),
+        Statement::Throw(expression) => {
+            let value = evaluate_expression(expression, Rc::clone(&environment))?;
+            Err(Error::Thrown(value))
+        }
+        Statement::Struct(name, fields) => {
+            let kind = Object::Struct(Rc::new(Struct {
+                name: name.to_owned(),
+                fields: fields.to_owned(),
+                methods: RefCell::default(),
+            }));
+            environment.borrow_mut().declare(name, kind.to_owned())?;
+            Ok(kind)
+        }
+        Statement::Enum(name, variants) => {
+            let variants = variants
+                .iter()
+                .map(|(variant, fields)| {
+                    let variant = Rc::new(Variant {
+                        enumeration: name.to_owned(),
+                        name: variant.to_owned(),
+                        arity: fields.len(),
+                    });
+                    if variant.arity == 0 {
+                        Object::Tagged(variant, vec![])
+                    } else {
+                        Object::Variant(variant)
+                    }
+                })
+                .collect::<Vec<Object>>();
+            for object in &variants {
+                if let Object::Variant(variant) | Object::Tagged(variant, _) = object {
+                    environment
+                        .borrow_mut()
+                        .declare(&variant.name, object.to_owned())?;
+                }
+            }
+            let kind = Object::Enum(Rc::new(Enum {
+                name: name.to_owned(),
+                variants,
+            }));
+            environment.borrow_mut().declare(name, kind.to_owned())?;
+            Ok(kind)
         }
         Statement::Expression(expression) => {
             evaluate_expression(expression, Rc::clone(&environment))
@@ -52,7 +145,32 @@ fn evaluate_statement(
     }
 }
 
-fn evaluate_expression(
+fn assign(
+    target: &Expression,
+    value: Object,
+    environment: &Rc<RefCell<Environment>>,
+) -> Result<Object, Error> {
+    match target {
+        Expression::Identifier(name) => environment.borrow_mut().mutate(name, value),
+        Expression::Index(object, index) => {
+            let container = evaluate_expression(object, Rc::clone(environment))?;
+            let index = evaluate_expression(index, Rc::clone(environment))?;
+            let container = evaluate_index_update(container, index, value.to_owned())?;
+            assign(object, container, environment)?;
+            Ok(value)
+        }
+        Expression::Member(object, name) => {
+            let container = evaluate_expression(object, Rc::clone(environment))?;
+            let index = Object::String(name.to_owned());
+            let container = evaluate_index_update(container, index, value.to_owned())?;
+            assign(object, container, environment)?;
+            Ok(value)
+        }
+        target => Err(Error::Runtime(format!("Can not assign to '{target}'"))),
+    }
+}
+
+pub(crate) fn evaluate_expression(
     expression: &Expression,
     environment: Rc<RefCell<Environment>>,
 ) -> Result<Object, Error> {
@@ -62,27 +180,51 @@ fn evaluate_expression(
             .get(name)
             .ok_or(Error::Runtime(format!("Name '{name}' is not defined"))),
         Expression::Integer(value) => Ok(Object::Integer(*value)),
+        Expression::BigInteger(value) => Ok(Object::from(value.to_owned())),
         Expression::Float(value) => Ok(Object::Float(*value)),
+        Expression::Decimal(value) => Ok(Object::Decimal(value.to_owned())),
         Expression::String(value) => Ok(Object::String(value.to_owned())),
         Expression::Boolean(value) => Ok(Object::Boolean(*value)),
-        Expression::Array(expressions) => {
+        Expression::Array(elements) => {
             let mut array = vec![];
-            for expression in expressions {
-                array.push(evaluate_expression(expression, Rc::clone(&environment))?);
+            for element in elements {
+                match element {
+                    Element::Value(value) => {
+                        array.push(evaluate_expression(value, Rc::clone(&environment))?)
+                    }
+                    Element::Spread(value) => array.extend(spread(evaluate_expression(
+                        value,
+                        Rc::clone(&environment),
+                    )?)?),
+                }
             }
-            Ok(Object::Array(array))
+            Ok(Object::Array(array.into()))
         }
-        Expression::Hash(pairs) => {
-            let mut hash = HashMap::new();
-            for (name, value) in pairs {
-                let name = evaluate_expression(name, Rc::clone(&environment))?;
-                match name {
-                    Object::String(name) => {
+        Expression::Hash(entries) => {
+            let mut hash = OrderedHash::new();
+            for entry in entries {
+                match entry {
+                    Entry::Pair(key, value) => {
+                        let key = evaluate_expression(key, Rc::clone(&environment))?;
+                        if !key.is_hashable() {
+                            return Err(Error::Runtime(format!("Can not use '{key}' as a key")));
+                        }
                         let value = evaluate_expression(value, Rc::clone(&environment))?;
-                        hash.insert(name, value);
+                        hash.insert(key, value);
                     }
-                    _ => {
-                        return Err(Error::Runtime(format!("Can not use '{name}' as a key")));
+                    Entry::Spread(value) => {
+                        match evaluate_expression(value, Rc::clone(&environment))? {
+                            Object::Hash(pairs) => {
+                                for (key, value) in pairs.iter() {
+                                    hash.insert(key.to_owned(), value.to_owned());
+                                }
+                            }
+                            object => {
+                                return Err(Error::Runtime(format!(
+                                    "Can not spread '{object}' into a hash"
+                                )))
+                            }
+                        }
                     }
                 }
             }
@@ -91,43 +233,66 @@ fn evaluate_expression(
         Expression::Index(object, index) => {
             let object = evaluate_expression(object, Rc::clone(&environment))?;
             let index = evaluate_expression(index, Rc::clone(&environment))?;
-            match (object, index) {
-                (Object::Array(array), Object::Integer(index)) => {
-                    let last_index = array.len() as i64 - 1;
-                    if index >= 0 && index <= last_index {
-                        Ok(array[index as usize].to_owned())
-                    } else {
-                        Err(Error::Runtime(format!("Index '{index}' not valid")))
-                    }
-                }
-                (Object::Array(array), Object::Range(from, to)) => {
-                    let last_index = array.len() as i64;
-                    if from >= 0 && from <= last_index && to >= 0 && to <= last_index {
-                        Ok(Object::Array(array[from as usize..to as usize].to_vec()))
-                    } else {
-                        Err(Error::Runtime(format!(
-                            "Index '{}' not valid",
-                            Object::Range(from, to)
-                        )))
-                    }
+            match metamethod(&object, "__index") {
+                Some(method) => evaluate_index(object.to_owned(), index.to_owned())
+                    .or_else(|_| call(method, vec![object, index], vec![])),
+                None => evaluate_index(object, index),
+            }
+        }
+        Expression::Propagate(value) => {
+            match evaluate_expression(value, Rc::clone(&environment))? {
+                error @ Object::Error(_) => Err(Error::Propagated(error)),
+                value => Ok(value),
+            }
+        }
+        Expression::Member(object, name) => {
+            let object = evaluate_expression(object, Rc::clone(&environment))?;
+            if let Some(value) = member(&object, name) {
+                return Ok(value);
+            }
+            match (method(&object, name), metamethod(&object, "__index")) {
+                (Some(method), _) => Ok(method),
+                (None, Some(index)) => {
+                    call(index, vec![object, Object::String(name.to_owned())], vec![])
                 }
-                (Object::Hash(pairs), Object::String(key)) => {
-                    if let Some(object) = pairs.get(&key) {
-                        Ok(object.to_owned())
-                    } else {
-                        Err(Error::Runtime(format!("Key '{key}' not valid",)))
-                    }
+                (None, None) => Err(Error::Runtime(format!(
+                    "Object '{object}' has no member '{name}'"
+                ))),
+            }
+        }
+        Expression::Slice(object, range, step) => {
+            let object = evaluate_expression(object, Rc::clone(&environment))?;
+            let range = evaluate_expression(range, Rc::clone(&environment))?;
+            let step = evaluate_expression(step, Rc::clone(&environment))?;
+            match (range, step) {
+                (Object::Range(_), Object::Integer(0)) => {
+                    Err(Error::Runtime("Slice step can not be zero".to_string()))
                 }
-                (object, index) => Err(Error::Runtime(format!(
-                    "Object '{object}' is not indexable with '{index}'"
+                (Object::Range(range), Object::Integer(step)) => evaluate_slice(
+                    object,
+                    Range {
+                        step: Some(step),
+                        ..range
+                    },
+                ),
+                (range, step) => Err(Error::Runtime(format!(
+                    "Can not slice with '{range}' and step '{step}'"
                 ))),
             }
         }
         Expression::Prefix(operator, right) => {
             let right = evaluate_expression(right, Rc::clone(&environment))?;
+            if let (Token::Minus, Some(method)) = (operator, metamethod(&right, "__neg")) {
+                return call(method, vec![right], vec![]);
+            }
             match (operator, right) {
-                (Token::Minus, Object::Integer(value)) => Ok(Object::Integer(-value)),
+                (Token::Minus, Object::Integer(value)) => Ok(value
+                    .checked_neg()
+                    .map(Object::Integer)
+                    .unwrap_or_else(|| Object::from(-&BigInt::from(value)))),
+                (Token::Minus, Object::BigInteger(value)) => Ok(Object::from(-&value)),
                 (Token::Minus, Object::Float(value)) => Ok(Object::Float(-value)),
+                (Token::Minus, Object::Decimal(value)) => Ok(Object::Decimal(-&value)),
                 (Token::Bang, Object::Boolean(value)) => Ok(Object::Boolean(!value)),
                 (operator, right) => Err(Error::Runtime(format!(
                     "Operator '{operator}' is not defined for '{right}'"
@@ -137,61 +302,119 @@ fn evaluate_expression(
         Expression::Infix(left, operator, right) => {
             let left = evaluate_expression(left, Rc::clone(&environment))?;
             let right = evaluate_expression(right, Rc::clone(&environment))?;
+            if let Some(result) = evaluate_overloaded_infix(&left, operator, &right)? {
+                return Ok(result);
+            }
             match (left, operator, right) {
-                (Object::Integer(from), Token::Spread, Object::Integer(to)) => {
-                    Ok(Object::Range(from, to))
-                }
-                (Object::Integer(left), Token::Plus, Object::Integer(right)) => {
-                    Ok(Object::Integer(left + right))
+                (left, Token::In, Object::Range(range)) => Ok(Object::Boolean(
+                    left.to_i64().is_some_and(|value| range.contains(value)),
+                )),
+                (left, Token::In, Object::Array(values)) => Ok(Object::Boolean(
+                    values.iter().any(|value| value.equal(&left)),
+                )),
+                (Object::String(left), Token::In, Object::String(right)) => {
+                    Ok(Object::Boolean(right.contains(&left)))
                 }
+                (left, Token::In, Object::Hash(pairs)) => Ok(Object::Boolean(
+                    left.is_hashable() && pairs.get(&left).is_some(),
+                )),
                 (Object::Float(left), Token::Plus, Object::Float(right)) => {
                     Ok(Object::Float(left + right))
                 }
+                (
+                    Object::Integer(left),
+                    operator @ (Token::Plus
+                    | Token::Minus
+                    | Token::Asterisk
+                    | Token::Slash
+                    | Token::Percent),
+                    Object::Integer(right),
+                ) => evaluate_integer_infix(left, operator, right),
+                (
+                    left @ (Object::Integer(_) | Object::BigInteger(_)),
+                    operator @ (Token::Plus
+                    | Token::Minus
+                    | Token::Asterisk
+                    | Token::Slash
+                    | Token::Percent),
+                    right @ (Object::Integer(_) | Object::BigInteger(_)),
+                ) => evaluate_big_integer_infix(
+                    left.to_big_integer().unwrap(),
+                    operator,
+                    right.to_big_integer().unwrap(),
+                ),
                 (Object::String(left), Token::Plus, Object::String(right)) => {
                     Ok(Object::String(left + &right))
                 }
-                (Object::Integer(left), Token::Minus, Object::Integer(right)) => {
-                    Ok(Object::Integer(left - right))
+                (Object::Array(left), Token::Plus, Object::Array(right)) => {
+                    let mut left = left.into_vec();
+                    left.extend(right);
+                    Ok(Object::Array(left.into()))
+                }
+                (Object::Hash(left), Token::Plus, Object::Hash(right)) => {
+                    let mut merged = OrderedHash::new();
+                    for (key, value) in left.iter().chain(right.iter()) {
+                        merged.insert(key.to_owned(), value.to_owned());
+                    }
+                    Ok(Object::Hash(merged))
+                }
+                (Object::String(value), Token::Asterisk, Object::Integer(count))
+                | (Object::Integer(count), Token::Asterisk, Object::String(value)) => {
+                    repetitions(count, value.len(), 1)?;
+                    Ok(Object::String(value.repeat(count as usize)))
+                }
+                (Object::Array(values), Token::Asterisk, Object::Integer(count))
+                | (Object::Integer(count), Token::Asterisk, Object::Array(values)) => {
+                    let length = repetitions(count, values.len(), size_of::<Object>())?;
+                    Ok(Object::Array(
+                        values.iter().cycle().take(length).cloned().collect(),
+                    ))
                 }
                 (Object::Float(left), Token::Minus, Object::Float(right)) => {
                     Ok(Object::Float(left - right))
                 }
-                (Object::Integer(left), Token::Asterisk, Object::Integer(right)) => {
-                    Ok(Object::Integer(left * right))
-                }
                 (Object::Float(left), Token::Asterisk, Object::Float(right)) => {
                     Ok(Object::Float(left * right))
                 }
-                (Object::Integer(left), Token::Slash, Object::Integer(right)) => {
-                    Ok(Object::Integer(left / right))
-                }
                 (Object::Float(left), Token::Slash, Object::Float(right)) => {
                     Ok(Object::Float(left / right))
                 }
-                (Object::Integer(left), Token::Lesser, Object::Integer(right)) => {
-                    Ok(Object::Boolean(left < right))
-                }
-                (Object::Float(left), Token::Lesser, Object::Float(right)) => {
-                    Ok(Object::Boolean(left < right))
-                }
-                (Object::Integer(left), Token::LesserOrEqual, Object::Integer(right)) => {
-                    Ok(Object::Boolean(left <= right))
-                }
-                (Object::Float(left), Token::LesserOrEqual, Object::Float(right)) => {
-                    Ok(Object::Boolean(left <= right))
-                }
-                (Object::Integer(left), Token::Greater, Object::Integer(right)) => {
-                    Ok(Object::Boolean(left > right))
-                }
-                (Object::Float(left), Token::Greater, Object::Float(right)) => {
-                    Ok(Object::Boolean(left > right))
-                }
-                (Object::Integer(left), Token::GreaterOrEqual, Object::Integer(right)) => {
-                    Ok(Object::Boolean(left >= right))
-                }
-                (Object::Float(left), Token::GreaterOrEqual, Object::Float(right)) => {
-                    Ok(Object::Boolean(left >= right))
+                (Object::Float(left), Token::Percent, Object::Float(right)) => {
+                    Ok(Object::Float(left % right))
                 }
+                (
+                    left,
+                    operator @ (Token::Lesser
+                    | Token::LesserOrEqual
+                    | Token::Greater
+                    | Token::GreaterOrEqual),
+                    right,
+                ) => Ok(Object::Boolean(is_ordered(operator, left.compare(&right)?))),
+                (
+                    left @ (Object::Decimal(_) | Object::Integer(_) | Object::BigInteger(_)),
+                    operator @ (Token::Plus
+                    | Token::Minus
+                    | Token::Asterisk
+                    | Token::Slash
+                    | Token::Percent),
+                    right @ (Object::Decimal(_) | Object::Integer(_) | Object::BigInteger(_)),
+                ) => evaluate_decimal_infix(
+                    left.to_decimal().unwrap(),
+                    operator,
+                    right.to_decimal().unwrap(),
+                ),
+                (
+                    left @ (Object::Decimal(_) | Object::Float(_)),
+                    operator @ (Token::Plus
+                    | Token::Minus
+                    | Token::Asterisk
+                    | Token::Slash
+                    | Token::Percent),
+                    right @ (Object::Decimal(_) | Object::Float(_)),
+                ) => Err(Error::Runtime(format!(
+                    "Can not mix '{left}' and '{right}' with '{operator}', \
+                    convert explicitly with 'decimal' or 'float'"
+                ))),
                 (Object::Integer(left), Token::Ampersand, Object::Integer(right)) => {
                     Ok(Object::Integer(left & right))
                 }
@@ -211,15 +434,55 @@ fn evaluate_expression(
                 ))),
             }
         }
-        Expression::Block(statements) => {
-            let mut value = Object::Null;
-            for statement in statements {
-                value = evaluate_statement(statement, Rc::clone(&environment))?;
-                if let Object::Return(value) = value {
-                    return Ok(*value);
+        Expression::Range(start, end, step, inclusive) => {
+            let start = evaluate_expression(start, Rc::clone(&environment))?;
+            let end = evaluate_expression(end, Rc::clone(&environment))?;
+            let step = match step {
+                Some(step) => Some(evaluate_expression(step, Rc::clone(&environment))?),
+                None => None,
+            };
+            match (start, end, step) {
+                (_, _, Some(Object::Integer(0))) => {
+                    Err(Error::Runtime("Range step can not be zero".to_string()))
                 }
+                (
+                    Object::Integer(start),
+                    Object::Integer(end),
+                    step @ (None | Some(Object::Integer(_))),
+                ) => Ok(Object::Range(Range {
+                    start,
+                    end,
+                    step: match step {
+                        Some(Object::Integer(step)) => Some(step),
+                        _ => None,
+                    },
+                    inclusive: *inclusive,
+                })),
+                (start, end, step) => Err(Error::Runtime(format!(
+                    "Can not make a range from '{start}' to '{end}'{}",
+                    step.map(|step| format!(" with step '{step}'"))
+                        .unwrap_or_default()
+                ))),
             }
-            Ok(value)
+        }
+        Expression::Block(statements) => evaluate_block(statements, environment),
+        Expression::Try(body, catch, finally) => {
+            let mut result = evaluate_expression(body, Rc::clone(&environment));
+            match (&result, catch) {
+                (Err(Error::Propagated(_) | Error::Return(_)), _) => {}
+                (Err(error), Some((name, handler))) => {
+                    let scope = Rc::new(RefCell::new(Environment::extend(Rc::clone(&environment))));
+                    if let Some(name) = name {
+                        scope.borrow_mut().set(name.to_owned(), error_object(error));
+                    }
+                    result = evaluate_expression(handler, scope);
+                }
+                _ => {}
+            }
+            if let Some(finally) = finally {
+                evaluate_expression(finally, Rc::clone(&environment))?;
+            }
+            result
         }
         Expression::If(condition, consequence, alternative) => {
             let condition = evaluate_expression(condition, Rc::clone(&environment))?;
@@ -233,40 +496,866 @@ fn evaluate_expression(
                 }
             }
         }
-        Expression::Function(parameters, body) => Ok(Object::Function(
-            parameters.to_owned(),
-            *body.to_owned(),
-            Rc::clone(&environment),
-        )),
+        Expression::Function(parameters, body) => Ok(Object::Function(Rc::new(Function {
+            parameters: parameters.to_owned(),
+            body: *body.to_owned(),
+            environment: Rc::clone(&environment),
+            generator: body.yields(),
+        }))),
         Expression::Call(callee, arguments) => {
-            let callee = evaluate_expression(callee, Rc::clone(&environment))?;
-            let mut _arguments = vec![];
+            let (callee, mut positional) = match callee.as_ref() {
+                Expression::Member(object, name) => {
+                    let object = evaluate_expression(object, Rc::clone(&environment))?;
+                    match member(&object, name) {
+                        Some(callee) => (callee, vec![]),
+                        None => {
+                            let callee = match method(&object, name) {
+                                Some(method) => method,
+                                None => environment.borrow().get(name).ok_or(Error::Runtime(
+                                    format!("Object '{object}' has no member '{name}'"),
+                                ))?,
+                            };
+                            (callee, vec![object])
+                        }
+                    }
+                }
+                callee => (
+                    evaluate_expression(callee, Rc::clone(&environment))?,
+                    vec![],
+                ),
+            };
+            let mut named = vec![];
             for argument in arguments {
-                let argument = evaluate_expression(argument, Rc::clone(&environment))?;
-                _arguments.push(argument);
-            }
-            match callee {
-                Object::Function(parameters, body, environment) => {
-                    if parameters.len() != _arguments.len() {
-                        Err(Error::Runtime(format!(
-                            "Expected {} argument(s), got {}",
-                            parameters.len(),
-                            _arguments.len()
-                        )))
-                    } else {
-                        let local_environment =
-                            Rc::new(RefCell::new(Environment::extend(environment)));
-                        for i in 0..parameters.len() {
-                            local_environment
-                                .borrow_mut()
-                                .set(parameters[i].to_owned(), _arguments[i].to_owned());
+                match argument {
+                    Argument::Positional(value) => {
+                        positional.push(evaluate_expression(value, Rc::clone(&environment))?)
+                    }
+                    Argument::Named(name, value) => named.push((
+                        name.to_owned(),
+                        evaluate_expression(value, Rc::clone(&environment))?,
+                    )),
+                    Argument::Spread(value) => {
+                        match evaluate_expression(value, Rc::clone(&environment))? {
+                            Object::Hash(pairs) => {
+                                for (key, value) in pairs.iter() {
+                                    match key {
+                                        Object::String(name) => {
+                                            named.push((name.to_owned(), value.to_owned()))
+                                        }
+                                        key => {
+                                            return Err(Error::Runtime(format!(
+                                                "Can not use '{key}' as an argument name"
+                                            )))
+                                        }
+                                    }
+                                }
+                            }
+                            object => positional.extend(spread(object)?),
                         }
-                        evaluate_expression(&body, local_environment)
                     }
                 }
-                Object::Builtin(func) => func(_arguments),
-                object => Err(Error::Runtime(format!("Object '{object}' is not callable"))),
+            }
+            call(callee, positional, named)
+        }
+        Expression::Match(value, arms) => {
+            let value = evaluate_expression(value, Rc::clone(&environment))?;
+            let (arm, scope) = select_arm(&value, arms, &environment)?;
+            evaluate_expression(&arm.body, scope)
+        }
+    }
+}
+
+pub(crate) fn select_arm<'a>(
+    value: &Object,
+    arms: &'a [MatchArm],
+    environment: &Rc<RefCell<Environment>>,
+) -> Result<(&'a MatchArm, Rc<RefCell<Environment>>), Error> {
+    for arm in arms {
+        let scope = Rc::new(RefCell::new(Environment::extend(Rc::clone(environment))));
+        if !match_pattern(&arm.pattern, value.to_owned(), &scope)? {
+            continue;
+        }
+        if let Some(guard) = &arm.guard {
+            if !evaluate_expression(guard, Rc::clone(&scope))?.is_truthy() {
+                continue;
             }
         }
+        return Ok((arm, scope));
+    }
+    Err(Error::Runtime(format!("No match arm matches '{value:#}'")))
+}
+
+pub(crate) fn spread(object: Object) -> Result<Vec<Object>, Error> {
+    match object {
+        Object::Array(values) => Ok(values.into_vec()),
+        object => Iter::new(object)?.collect(),
+    }
+}
+
+fn error_object(error: &Error) -> Object {
+    let (kind, message) = match error {
+        Error::Thrown(value) | Error::Propagated(value) | Error::Return(value) => {
+            return value.to_owned()
+        }
+        Error::Lexer(message) => ("LexerError", message),
+        Error::Parser(message) => ("ParserError", message),
+        Error::Runtime(message) => ("RuntimeError", message),
+    };
+    Object::Error(Rc::new(ErrorValue::new(
+        kind,
+        message.to_owned(),
+        Object::Null,
+    )))
+}
+
+pub(crate) fn call(
+    callee: Object,
+    positional: Vec<Object>,
+    named: Vec<(String, Object)>,
+) -> Result<Object, Error> {
+    match callee {
+        Object::Function(function) if function.generator => {
+            let environment = bind_arguments(&function, positional, named)?;
+            Ok(Object::Generator(Rc::new(RefCell::new(Generator::new(
+                &function.body,
+                environment,
+            )))))
+        }
+        Object::Function(function) => {
+            let environment = bind_arguments(&function, positional, named)?;
+            match evaluate_expression(&function.body, environment) {
+                Err(Error::Propagated(value) | Error::Return(value)) => Ok(value),
+                result => result,
+            }
+        }
+        Object::Builtin(_) if !named.is_empty() => Err(Error::Runtime(
+            "Builtin functions do not accept named arguments".to_string(),
+        )),
+        Object::Builtin(func) => func(positional),
+        Object::Struct(kind) => construct(kind, positional, named),
+        Object::Variant(_) if !named.is_empty() => Err(Error::Runtime(
+            "Enum variants do not accept named arguments".to_string(),
+        )),
+        Object::Variant(variant) if positional.len() != variant.arity => {
+            Err(Error::Runtime(format!(
+                "Variant '{}' expects {} value(s), got {}",
+                variant.name,
+                variant.arity,
+                positional.len()
+            )))
+        }
+        Object::Variant(variant) => Ok(Object::Tagged(variant, positional)),
+        object => Err(Error::Runtime(format!("Object '{object}' is not callable"))),
+    }
+}
+
+fn construct(
+    kind: Rc<Struct>,
+    positional: Vec<Object>,
+    named: Vec<(String, Object)>,
+) -> Result<Object, Error> {
+    if positional.len() > kind.fields.len() {
+        return Err(Error::Runtime(format!(
+            "Expected {} argument(s), got {}",
+            kind.fields.len(),
+            positional.len()
+        )));
+    }
+    let mut values = positional
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<Option<Object>>>();
+    values.resize(kind.fields.len(), None);
+    for (name, value) in named {
+        let position = kind.position(&name).ok_or(Error::Runtime(format!(
+            "Struct '{}' has no field '{name}'",
+            kind.name
+        )))?;
+        if values[position].is_some() {
+            return Err(Error::Runtime(format!(
+                "Field '{name}' given more than once"
+            )));
+        }
+        values[position] = Some(value);
+    }
+    let values = values
+        .into_iter()
+        .zip(&kind.fields)
+        .map(|(value, field)| {
+            value.ok_or(Error::Runtime(format!(
+                "Missing field '{field}' for '{}'",
+                kind.name
+            )))
+        })
+        .collect::<Result<Vec<Object>, Error>>()?;
+    Ok(Object::Record(kind, values))
+}
+
+fn bind_arguments(
+    function: &Function,
+    positional: Vec<Object>,
+    named: Vec<(String, Object)>,
+) -> Result<Rc<RefCell<Environment>>, Error> {
+    let parameters = &function.parameters;
+    let maximum = parameters
+        .iter()
+        .filter(|parameter| !parameter.rest)
+        .count();
+    let variadic = maximum < parameters.len();
+    if !variadic && positional.len() > maximum {
+        let required = parameters
+            .iter()
+            .filter(|parameter| parameter.default.is_none())
+            .count();
+        return Err(Error::Runtime(if required == maximum {
+            format!("Expected {maximum} argument(s), got {}", positional.len())
+        } else {
+            format!(
+                "Expected {required} to {maximum} argument(s), got {}",
+                positional.len()
+            )
+        }));
+    }
+
+    let mut positional = positional.into_iter();
+    let mut arguments = parameters
+        .iter()
+        .map(|parameter| {
+            if parameter.rest {
+                Some(Object::Array(positional.by_ref().collect()))
+            } else {
+                positional.next()
+            }
+        })
+        .collect::<Vec<Option<Object>>>();
+    for (name, value) in named {
+        let index = parameters
+            .iter()
+            .position(|parameter| {
+                !parameter.rest
+                    && matches!(&parameter.pattern, Pattern::Identifier(identifier) if *identifier == name)
+            })
+            .ok_or(Error::Runtime(format!("Unexpected argument '{name}'")))?;
+        if arguments[index].is_some() {
+            return Err(Error::Runtime(format!(
+                "Argument '{name}' given more than once"
+            )));
+        }
+        arguments[index] = Some(value);
+    }
+
+    let environment = Rc::new(RefCell::new(Environment::extend(Rc::clone(
+        &function.environment,
+    ))));
+    for (parameter, argument) in parameters.iter().zip(arguments) {
+        let value = match (argument, &parameter.default) {
+            (Some(value), _) => value,
+            (None, Some(default)) => evaluate_expression(default, Rc::clone(&environment))?,
+            (None, None) => {
+                return Err(Error::Runtime(format!(
+                    "Missing argument '{}'",
+                    parameter.pattern
+                )))
+            }
+        };
+        bind_pattern(&parameter.pattern, value, &environment)?;
+    }
+    Ok(environment)
+}
+
+fn bind_pattern(
+    pattern: &Pattern,
+    value: Object,
+    environment: &Rc<RefCell<Environment>>,
+) -> Result<(), Error> {
+    if match_pattern(pattern, value.to_owned(), environment)? {
+        Ok(())
+    } else {
+        Err(Error::Runtime(format!(
+            "Can not destructure '{value:#}' into '{pattern}'"
+        )))
+    }
+}
+
+fn match_pattern(
+    pattern: &Pattern,
+    value: Object,
+    environment: &Rc<RefCell<Environment>>,
+) -> Result<bool, Error> {
+    match (pattern, value) {
+        (Pattern::Wildcard, _) => Ok(true),
+        (Pattern::Identifier(name), value) => {
+            let bound = environment.borrow().get(name);
+            match bound {
+                Some(unit @ Object::Tagged(_, _)) if is_variant(&unit, name) => {
+                    Ok(unit.equal(&value))
+                }
+                _ => {
+                    environment.borrow_mut().declare(name, value)?;
+                    Ok(true)
+                }
+            }
+        }
+        (Pattern::Variant(name, patterns), value) => {
+            let bound = environment.borrow().get(name);
+            let expected = match bound {
+                Some(object) if is_variant(&object, name) => object,
+                _ => {
+                    return Err(Error::Runtime(format!(
+                        "Name '{name}' is not an enum variant"
+                    )))
+                }
+            };
+            match (expected, value) {
+                (
+                    Object::Variant(expected) | Object::Tagged(expected, _),
+                    Object::Tagged(variant, values),
+                ) if Rc::ptr_eq(&expected, &variant) && values.len() == patterns.len() => {
+                    for (pattern, value) in patterns.iter().zip(values) {
+                        if !match_pattern(pattern, value, environment)? {
+                            return Ok(false);
+                        }
+                    }
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+        (Pattern::Literal(literal), value) => {
+            let literal = evaluate_expression(literal, Rc::clone(environment))?;
+            Ok(literal.equal(&value))
+        }
+        (Pattern::Range(start, end, inclusive), value) => {
+            let start = evaluate_expression(start, Rc::clone(environment))?;
+            let end = evaluate_expression(end, Rc::clone(environment))?;
+            let below = match value.compare(&end) {
+                Ok(ordering) if *inclusive => ordering.is_le(),
+                Ok(ordering) => ordering.is_lt(),
+                Err(_) => false,
+            };
+            Ok(below && value.compare(&start).is_ok_and(Ordering::is_ge))
+        }
+        (Pattern::Array(patterns, rest), Object::Array(values)) => {
+            if values.len() < patterns.len() || (rest.is_none() && values.len() > patterns.len()) {
+                return Ok(false);
+            }
+            let mut values = values.into_iter();
+            for pattern in patterns {
+                if !match_pattern(pattern, values.next().unwrap(), environment)? {
+                    return Ok(false);
+                }
+            }
+            if let Some(rest) = rest {
+                environment
+                    .borrow_mut()
+                    .declare(rest, Object::Array(values.collect()))?;
+            }
+            Ok(true)
+        }
+        (Pattern::Hash(..), Object::Record(kind, values)) => {
+            let mut pairs = OrderedHash::new();
+            for (field, value) in kind.fields.iter().zip(values) {
+                pairs.insert(Object::String(field.to_owned()), value);
+            }
+            match_pattern(pattern, Object::Hash(pairs), environment)
+        }
+        (Pattern::Hash(entries, rest), Object::Hash(pairs)) => {
+            for (key, pattern) in entries {
+                match pairs.get(&Object::String(key.to_owned())) {
+                    Some(value) if match_pattern(pattern, value.to_owned(), environment)? => {}
+                    _ => return Ok(false),
+                }
+            }
+            if let Some(rest) = rest {
+                let mut remaining = OrderedHash::new();
+                for (key, value) in pairs.iter() {
+                    let taken = matches!(key, Object::String(key) if entries.iter().any(|(name, _)| name == key));
+                    if !taken {
+                        remaining.insert(key.to_owned(), value.to_owned());
+                    }
+                }
+                environment
+                    .borrow_mut()
+                    .declare(rest, Object::Hash(remaining))?;
+            }
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+fn evaluate_overloaded_infix(
+    left: &Object,
+    operator: &Token,
+    right: &Object,
+) -> Result<Option<Object>, Error> {
+    let (name, swapped, negated) = match operator {
+        Token::Plus => ("__add", false, false),
+        Token::Minus => ("__sub", false, false),
+        Token::Asterisk => ("__mul", false, false),
+        Token::Slash => ("__div", false, false),
+        Token::Percent => ("__mod", false, false),
+        Token::Equal => ("__eq", false, false),
+        Token::NotEqual => ("__eq", false, true),
+        Token::Lesser => ("__lt", false, false),
+        Token::Greater => ("__lt", true, false),
+        Token::LesserOrEqual => ("__lt", true, true),
+        Token::GreaterOrEqual => ("__lt", false, true),
+        _ => return Ok(None),
+    };
+    let (left, right) = if swapped {
+        (right, left)
+    } else {
+        (left, right)
+    };
+    let method = match metamethod(left, name).or_else(|| metamethod(right, name)) {
+        Some(method) => method,
+        None => return Ok(None),
+    };
+    let result = call(method, vec![left.to_owned(), right.to_owned()], vec![])?;
+    if negated {
+        Ok(Some(Object::Boolean(!result.is_truthy())))
+    } else {
+        Ok(Some(result))
+    }
+}
+
+const METAMETHODS: [&str; 11] = [
+    "__add", "__sub", "__mul", "__div", "__mod", "__neg", "__eq", "__lt", "__index", "__str",
+    "__next",
+];
+
+pub(crate) fn metamethod(object: &Object, name: &str) -> Option<Object> {
+    match object {
+        Object::Hash(pairs) => pairs.get(&Object::String(name.to_owned())).cloned(),
+        object => method(object, name),
+    }
+}
+
+fn method(object: &Object, name: &str) -> Option<Object> {
+    match object {
+        Object::Record(kind, _) => kind.methods.borrow().get(name).cloned(),
+        _ => None,
+    }
+}
+
+pub(crate) fn stringify(object: &Object) -> Result<String, Error> {
+    let failure = RefCell::new(None);
+    let mut output = String::new();
+    match write!(output, "{}", Displayed(object, &failure)) {
+        Ok(()) => Ok(output),
+        Err(_) => Err(failure
+            .take()
+            .unwrap_or(Error::Runtime(format!("Can not display '{object}'")))),
+    }
+}
+
+struct Displayed<'a>(&'a Object, &'a RefCell<Option<Error>>);
+
+impl fmt::Display for Displayed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Displayed(object, failure) = *self;
+        let method = match metamethod(object, "__str") {
+            Some(method) => method,
+            None => return object.format(f, &|value, f| Displayed(value, failure).fmt(f)),
+        };
+        match call(method, vec![object.to_owned()], vec![]) {
+            Ok(Object::String(value)) => write!(f, "{value}"),
+            Ok(value) => {
+                failure.replace(Some(Error::Runtime(format!(
+                    "Metamethod '__str' must return a string, got '{value}'"
+                ))));
+                Err(fmt::Error)
+            }
+            Err(error) => {
+                failure.replace(Some(error));
+                Err(fmt::Error)
+            }
+        }
+    }
+}
+
+fn member(object: &Object, name: &str) -> Option<Object> {
+    match object {
+        Object::Hash(pairs) => pairs.get(&Object::String(name.to_owned())).cloned(),
+        Object::Record(kind, values) => kind
+            .position(name)
+            .map(|position| values[position].to_owned()),
+        Object::Error(error) => match name {
+            "kind" => Some(Object::String(error.kind.to_owned())),
+            "message" => Some(Object::String(error.message.to_owned())),
+            "data" => Some(error.data.to_owned()),
+            _ => None,
+        },
+        Object::Enum(kind) => kind
+            .variants
+            .iter()
+            .find(|variant| is_variant(variant, name))
+            .cloned(),
+        _ => None,
+    }
+}
+
+fn is_variant(object: &Object, name: &str) -> bool {
+    matches!(object, Object::Variant(variant) | Object::Tagged(variant, _) if variant.name == name)
+}
+
+fn evaluate_index(object: Object, index: Object) -> Result<Object, Error> {
+    match (object, index) {
+        (Object::Array(array), Object::Integer(index)) => {
+            let position = index_position(index, array.len())?;
+            Ok(array[position].to_owned())
+        }
+        (Object::String(string), Object::Integer(index)) => {
+            let characters = string.chars().collect::<Vec<char>>();
+            let position = index_position(index, characters.len())?;
+            Ok(Object::String(characters[position].to_string()))
+        }
+        (object @ (Object::Array(_) | Object::String(_)), Object::Range(range)) => {
+            evaluate_slice(object, range)
+        }
+        (Object::Hash(pairs), key) if key.is_hashable() => {
+            if let Some(object) = pairs.get(&key) {
+                Ok(object.to_owned())
+            } else {
+                Err(Error::Runtime(format!("Key '{key}' not valid",)))
+            }
+        }
+        (Object::Record(kind, values), Object::String(field)) => {
+            let position = field_position(&kind, &field)?;
+            Ok(values[position].to_owned())
+        }
+        (Object::Enum(kind), Object::String(name)) => kind
+            .variants
+            .iter()
+            .find(|variant| is_variant(variant, &name))
+            .cloned()
+            .ok_or(Error::Runtime(format!(
+                "Enum '{}' has no variant '{name}'",
+                kind.name
+            ))),
+        (object, index) => Err(Error::Runtime(format!(
+            "Object '{object}' is not indexable with '{index}'"
+        ))),
+    }
+}
+
+fn evaluate_index_update(object: Object, index: Object, value: Object) -> Result<Object, Error> {
+    match (object, index) {
+        (Object::Array(array), _) if array.is_frozen() => Err(Error::Runtime(format!(
+            "Can not mutate frozen array '{}'",
+            Object::Array(array)
+        ))),
+        (Object::Hash(pairs), _) if pairs.is_frozen() => Err(Error::Runtime(format!(
+            "Can not mutate frozen hash '{}'",
+            Object::Hash(pairs)
+        ))),
+        (Object::Array(array), Object::Integer(index)) => {
+            let mut array = array.into_vec();
+            let position = index_position(index, array.len())?;
+            array[position] = value;
+            Ok(Object::Array(array.into()))
+        }
+        (Object::Hash(mut pairs), key) if key.is_hashable() => {
+            pairs.insert(key, value);
+            Ok(Object::Hash(pairs))
+        }
+        (Object::Record(kind, mut values), Object::String(field)) => {
+            let position = field_position(&kind, &field)?;
+            values[position] = value;
+            Ok(Object::Record(kind, values))
+        }
+        (Object::Struct(kind), Object::String(name)) if METAMETHODS.contains(&name.as_str()) => {
+            kind.methods.borrow_mut().insert(name, value);
+            Ok(Object::Struct(kind))
+        }
+        (Object::Struct(kind), Object::String(name)) => Err(Error::Runtime(format!(
+            "Struct '{}' has no metamethod '{name}'",
+            kind.name
+        ))),
+        (object, index) => Err(Error::Runtime(format!(
+            "Object '{object}' is not assignable with '{index}'"
+        ))),
+    }
+}
+
+fn field_position(kind: &Struct, field: &str) -> Result<usize, Error> {
+    kind.position(field).ok_or(Error::Runtime(format!(
+        "Struct '{}' has no field '{field}'",
+        kind.name
+    )))
+}
+
+fn evaluate_slice(object: Object, range: Range) -> Result<Object, Error> {
+    match object {
+        Object::Array(array) => Ok(Object::Array(
+            slice_positions(range, array.len())?
+                .map(|position| array[position].to_owned())
+                .collect(),
+        )),
+        Object::String(string) => {
+            let characters = string.chars().collect::<Vec<char>>();
+            Ok(Object::String(
+                slice_positions(range, characters.len())?
+                    .map(|position| characters[position])
+                    .collect(),
+            ))
+        }
+        object => Err(Error::Runtime(format!(
+            "Object '{object}' is not indexable with '{range}'"
+        ))),
+    }
+}
+
+fn index_position(index: i64, length: usize) -> Result<usize, Error> {
+    let position = if index < 0 {
+        index + length as i64
+    } else {
+        index
+    };
+    if (0..length as i64).contains(&position) {
+        Ok(position as usize)
+    } else {
+        Err(Error::Runtime(format!(
+            "Index '{index}' out of range for length {length}"
+        )))
+    }
+}
+
+fn slice_positions(range: Range, length: usize) -> Result<impl Iterator<Item = usize>, Error> {
+    let length = length as i64;
+    let bound = |bound: i64| if bound < 0 { bound + length } else { bound };
+    let positions = Range {
+        start: bound(range.start),
+        end: bound(range.end),
+        ..range
+    };
+    let valid =
+        |position: Option<i64>| position.is_none_or(|position| (0..length).contains(&position));
+    if valid(positions.first()) && valid(positions.last()) {
+        Ok(positions.iter().map(|position| position as usize))
+    } else {
+        Err(Error::Runtime(format!(
+            "Range '{range}' out of range for length {length}"
+        )))
+    }
+}
+
+fn repetitions(count: i64, length: usize, unit: usize) -> Result<usize, Error> {
+    usize::try_from(count)
+        .ok()
+        .and_then(|count| length.checked_mul(count))
+        .filter(|total| {
+            total
+                .checked_mul(unit)
+                .is_some_and(|size| size <= isize::MAX as usize)
+        })
+        .ok_or(Error::Runtime(format!(
+            "Can not repeat a value '{count}' times"
+        )))
+}
+
+fn evaluate_integer_infix(left: i64, operator: &Token, right: i64) -> Result<Object, Error> {
+    let value = match operator {
+        Token::Plus => left.checked_add(right),
+        Token::Minus => left.checked_sub(right),
+        Token::Asterisk => left.checked_mul(right),
+        Token::Slash | Token::Percent if right == 0 => {
+            return Err(Error::Runtime("Division by zero".to_string()))
+        }
+        Token::Slash => left.checked_div(right),
+        _ => left.checked_rem(right),
+    };
+    match value {
+        Some(value) => Ok(Object::Integer(value)),
+        None => evaluate_big_integer_infix(BigInt::from(left), operator, BigInt::from(right)),
+    }
+}
+
+fn evaluate_big_integer_infix(
+    left: BigInt,
+    operator: &Token,
+    right: BigInt,
+) -> Result<Object, Error> {
+    let value = match operator {
+        Token::Plus => &left + &right,
+        Token::Minus => &left - &right,
+        Token::Asterisk => &left * &right,
+        _ => {
+            let (quotient, remainder) = left
+                .div_rem(&right)
+                .ok_or(Error::Runtime("Division by zero".to_string()))?;
+            if let Token::Slash = operator {
+                quotient
+            } else {
+                remainder
+            }
+        }
+    };
+    Ok(Object::from(value))
+}
+
+fn evaluate_decimal_infix(
+    left: Decimal,
+    operator: &Token,
+    right: Decimal,
+) -> Result<Object, Error> {
+    let value = match operator {
+        Token::Plus => &left + &right,
+        Token::Minus => &left - &right,
+        Token::Asterisk => &left * &right,
+        Token::Slash => left
+            .div(&right, decimal::context())
+            .ok_or(Error::Runtime("Division by zero".to_string()))?,
+        _ => left
+            .rem(&right)
+            .ok_or(Error::Runtime("Division by zero".to_string()))?,
+    };
+    Ok(Object::Decimal(value))
+}
+
+fn is_ordered(operator: &Token, ordering: Ordering) -> bool {
+    match operator {
+        Token::Lesser => ordering.is_lt(),
+        Token::LesserOrEqual => ordering.is_le(),
+        Token::Greater => ordering.is_gt(),
+        _ => ordering.is_ge(),
+    }
+}
+
+/// Runs a source snippet with the builtins and returns the repr of its last value.
+#[cfg(test)]
+pub(crate) fn run(source: &str) -> Result<String, Error> {
+    use crate::frontend::{lexer::Lexer, parser::Parser};
+
+    let tokens = Lexer::new(source.to_string()).lex()?;
+    let program = Parser::new(tokens).parse_program()?;
+    let environment = Rc::new(RefCell::new(super::builtin::get_builtin()));
+    Ok(evaluate(&program, environment)?.repr())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::common::error::Error;
+
+    fn runtime_error(source: &str) -> String {
+        match run(source) {
+            Err(Error::Runtime(message)) => message,
+            result => panic!("expected a runtime error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn match_selects_the_first_arm_whose_guard_holds() {
+        let source = "
+            let classify = |v| match v {
+                0 => \"zero\",
+                [x, y] if x == y => \"pair\",
+                [x, ...rest] => len(rest),
+                n if n < 0 => \"negative\",
+                _ => \"other\",
+            }
+            array[classify(0), classify(-3), classify(array[2, 2]), classify(array[1, 2, 3]), classify(7)]
+        ";
+        assert_eq!(
+            run(source).unwrap(),
+            "array[\"zero\", \"negative\", \"pair\", 2, \"other\"]"
+        );
+    }
+
+    #[test]
+    fn match_without_a_matching_arm_fails() {
+        assert_eq!(
+            runtime_error("match 5 { 0 => 1, n if n > 9 => 2 }"),
+            "No match arm matches '5'"
+        );
+    }
+
+    #[test]
+    fn try_runs_catch_then_finally() {
+        let source = "
+            let log = array[]
+            let f = || {
+                try {
+                    mut log = append(log, \"try\")
+                    throw \"boom\"
+                } catch e {
+                    mut log = append(log, \"catch \" + e)
+                } finally {
+                    mut log = append(log, \"finally\")
+                }
+                mut log = append(log, \"after\")
+            }
+            f()
+            log
+        ";
+        assert_eq!(
+            run(source).unwrap(),
+            "array[\"try\", \"catch boom\", \"finally\", \"after\"]"
+        );
+    }
+
+    #[test]
+    fn finally_runs_before_return() {
+        let source = "
+            let log = array[]
+            let f = || { try { return 7 } finally { mut log = append(log, \"finally\") } }
+            array[f(), log]
+        ";
+        assert_eq!(run(source).unwrap(), "array[7, array[\"finally\"]]");
+    }
+
+    #[test]
+    fn defer_runs_in_reverse_order_on_exit() {
+        let source = "
+            let log = array[]
+            let f = || {
+                defer { mut log = append(log, 1) }
+                defer { mut log = append(log, 2) }
+                mut log = append(log, 0)
+                return 3
+            }
+            array[f(), log]
+        ";
+        assert_eq!(run(source).unwrap(), "array[3, array[0, 2, 1]]");
+    }
+
+    #[test]
+    fn defer_errors_override_return_but_not_earlier_errors() {
+        let source = "
+            let f = || { defer { throw \"deferred\" }  return 1 }
+            let g = || { defer { throw \"deferred\" }  throw \"first\" }
+            array[try { f() } catch e { e }, try { g() } catch e { e }]
+        ";
+        assert_eq!(run(source).unwrap(), "array[\"deferred\", \"first\"]");
+    }
+
+    #[test]
+    fn constants_can_not_be_reassigned_or_redeclared() {
+        assert_eq!(
+            runtime_error("const c = 1  mut c = 2"),
+            "Can not assign to constant 'c'"
+        );
+        assert_eq!(
+            runtime_error("const c = 1  let c = 2"),
+            "Can not redeclare constant 'c'"
+        );
+        assert_eq!(
+            runtime_error("const c = 1  let [a, c] = array[1, 2]"),
+            "Can not redeclare constant 'c'"
+        );
+    }
+
+    #[test]
+    fn frozen_values_reject_mutation() {
+        assert_eq!(
+            runtime_error("let a = freeze(array[1, array[2]])  mut a[1][0] = 5"),
+            "Can not mutate frozen array '[2]'"
+        );
+        assert_eq!(
+            runtime_error("let h = freeze(hash[\"k\": 1])  mut h[\"k\"] = 5"),
+            "Can not mutate frozen hash '{k: 1}'"
+        );
     }
 }
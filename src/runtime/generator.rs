@@ -0,0 +1,269 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::common::{
+    ast::{Expression, Statement},
+    error::Error,
+    object::Object,
+};
+
+use super::{
+    environment::Environment,
+    evaluator::{evaluate_expression, evaluate_statement, run_deferred, select_arm},
+    iterator::Iter,
+};
+
+#[derive(Debug)]
+struct Frame {
+    statements: Vec<Statement>,
+    position: usize,
+    environment: Rc<RefCell<Environment>>,
+    deferred: Vec<Expression>,
+    root: bool,
+}
+
+impl Frame {
+    fn new(statements: Vec<Statement>, environment: Rc<RefCell<Environment>>, root: bool) -> Frame {
+        Frame {
+            statements,
+            position: 0,
+            environment,
+            deferred: vec![],
+            root,
+        }
+    }
+
+    fn close(self, mut result: Result<(), Error>) -> Result<(), Error> {
+        for expression in self.deferred.iter().rev() {
+            result = run_deferred(expression, Rc::clone(&self.environment), result);
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Generator {
+    frames: Vec<Frame>,
+    delegate: Option<Iter>,
+}
+
+impl Generator {
+    pub(crate) fn new(body: &Expression, environment: Rc<RefCell<Environment>>) -> Generator {
+        let statements = match body {
+            Expression::Block(statements) => statements.to_owned(),
+            body => vec![Statement::Expression(body.to_owned())],
+        };
+        Generator {
+            frames: vec![Frame::new(statements, environment, true)],
+            delegate: None,
+        }
+    }
+
+    fn resume(&mut self) -> Result<Option<Object>, Error> {
+        loop {
+            let result = match self.step() {
+                Ok(Some(value)) => return Ok(Some(value)),
+                result => self.unwind(result.map(|_| ())),
+            };
+            match result {
+                Err(Error::Return(_)) if !self.frames.is_empty() => continue,
+                Ok(()) | Err(Error::Return(_)) => return Ok(None),
+                Err(Error::Propagated(value)) => return Ok(Some(value)),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn step(&mut self) -> Result<Option<Object>, Error> {
+        loop {
+            if let Some(delegate) = &mut self.delegate {
+                match delegate.next()? {
+                    Some(value) => return Ok(Some(value)),
+                    None => self.delegate = None,
+                }
+            }
+
+            let frame = match self.frames.last_mut() {
+                Some(frame) => frame,
+                None => return Ok(None),
+            };
+            let statement = match frame.statements.get(frame.position) {
+                Some(statement) => statement.to_owned(),
+                None => {
+                    self.frames.pop().unwrap().close(Ok(()))?;
+                    continue;
+                }
+            };
+            frame.position += 1;
+            let environment = Rc::clone(&frame.environment);
+
+            match statement {
+                Statement::Defer(expression) => frame.deferred.push(expression),
+                Statement::Yield(value, false) => {
+                    return Ok(Some(evaluate_expression(&value, environment)?));
+                }
+                Statement::Yield(value, true) => match evaluate_expression(&value, environment)? {
+                    Object::Generator(generator) => self.splice(&generator)?,
+                    value => self.delegate = Some(Iter::new(value)?),
+                },
+                Statement::Expression(expression) => self.enter(&expression, environment)?,
+                statement if statement.yields() => {
+                    return Err(Error::Runtime(
+                        "Yield is only allowed in statement position".to_string(),
+                    ));
+                }
+                statement => {
+                    evaluate_statement(&statement, environment)?;
+                }
+            }
+        }
+    }
+
+    fn enter(
+        &mut self,
+        expression: &Expression,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<(), Error> {
+        match expression {
+            Expression::Block(statements) => {
+                self.frames
+                    .push(Frame::new(statements.to_owned(), environment, false));
+                Ok(())
+            }
+            expression if !expression.yields() => {
+                evaluate_expression(expression, environment)?;
+                Ok(())
+            }
+            Expression::If(condition, consequence, alternative) => {
+                if evaluate_expression(condition, Rc::clone(&environment))?.is_truthy() {
+                    self.enter(consequence, environment)
+                } else if let Some(alternative) = alternative {
+                    self.enter(alternative, environment)
+                } else {
+                    Ok(())
+                }
+            }
+            Expression::Match(value, arms) => {
+                let value = evaluate_expression(value, Rc::clone(&environment))?;
+                let (arm, scope) = select_arm(&value, arms, &environment)?;
+                self.enter(&arm.body, scope)
+            }
+            Expression::Try(..) => Err(Error::Runtime(
+                "Yield is not allowed inside try".to_string(),
+            )),
+            _ => Err(Error::Runtime(
+                "Yield is only allowed in statement position".to_string(),
+            )),
+        }
+    }
+
+    /// Delegates to another generator by moving its frames onto this one, so that
+    /// recursive `yield ...` runs in constant stack space. A finished frame without
+    /// deferred expressions is dropped first, which keeps tail delegation flat.
+    fn splice(&mut self, generator: &Rc<RefCell<Generator>>) -> Result<(), Error> {
+        let mut generator = generator
+            .try_borrow_mut()
+            .map_err(|_| Error::Runtime("Generator is already running".to_string()))?;
+        if let Some(frame) = self.frames.last() {
+            if frame.position == frame.statements.len() && frame.deferred.is_empty() {
+                self.frames.pop();
+            }
+        }
+        self.frames.append(&mut generator.frames);
+        self.delegate = generator.delegate.take();
+        Ok(())
+    }
+
+    /// Closes frames after `step` stops. `return` and `?` only end the innermost
+    /// generator body, so unwinding stops at its root frame; any other error closes
+    /// every frame.
+    fn unwind(&mut self, mut result: Result<(), Error>) -> Result<(), Error> {
+        self.delegate = None;
+        while let Some(frame) = self.frames.pop() {
+            let root = frame.root;
+            result = frame.close(result);
+            if root && matches!(result, Err(Error::Return(_) | Error::Propagated(_))) {
+                break;
+            }
+        }
+        result
+    }
+}
+
+pub(crate) fn resume(generator: &Rc<RefCell<Generator>>) -> Result<Option<Object>, Error> {
+    generator
+        .try_borrow_mut()
+        .map_err(|_| Error::Runtime("Generator is already running".to_string()))?
+        .resume()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{common::error::Error, runtime::evaluator::run};
+
+    #[test]
+    fn yields_values_in_order() {
+        let source = "let g = || { yield 1  yield 2  yield 3 }  collect(g())";
+        assert_eq!(run(source).unwrap(), "array[1, 2, 3]");
+    }
+
+    #[test]
+    fn delegates_to_generators_and_iterables() {
+        let source = "
+            let inner = || { yield 2  return 0  yield 99 }
+            let outer = || { yield 1  yield ...inner()  yield ...array[3, 4] }
+            collect(outer())
+        ";
+        assert_eq!(run(source).unwrap(), "array[1, 2, 3, 4]");
+    }
+
+    #[test]
+    fn recursive_delegation_does_not_grow_the_stack() {
+        let source = "
+            let naturals = |n| { yield n  yield ...naturals(n + 1) }
+            len(collect(take(naturals(0), 20000)))
+        ";
+        assert_eq!(run(source).unwrap(), "20000");
+    }
+
+    #[test]
+    fn next_returns_default_or_stop_iteration() {
+        let source = "
+            let g = || { yield 1 }
+            let it = g()
+            array[next(it), next(it, \"done\"), next(it)]
+        ";
+        assert_eq!(
+            run(source).unwrap(),
+            "array[1, \"done\", StopIteration: Generator is exhausted]"
+        );
+    }
+
+    #[test]
+    fn propagated_errors_are_handed_to_the_caller() {
+        let source = "let g = || { yield 1  error(\"bad\")?  yield 2 }  collect(g())";
+        assert_eq!(run(source).unwrap(), "array[1, Error: bad]");
+    }
+
+    #[test]
+    fn runs_deferred_expressions_on_exhaustion() {
+        let source = "
+            let log = array[]
+            let g = || { defer { mut log = append(log, \"closed\") }  yield 1 }
+            let it = g()
+            next(it)
+            let before = log
+            next(it, 0)
+            array[before, log]
+        ";
+        assert_eq!(run(source).unwrap(), "array[array[], array[\"closed\"]]");
+    }
+
+    #[test]
+    fn rejects_resuming_a_running_generator() {
+        let source = "let g = || { yield next(s) }  let s = g()  next(s)";
+        assert!(matches!(
+            run(source),
+            Err(Error::Runtime(message)) if message == "Generator is already running"
+        ));
+    }
+}
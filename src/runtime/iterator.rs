@@ -0,0 +1,194 @@
+use std::{cell::RefCell, rc::Rc, vec::IntoIter};
+
+use crate::common::{error::Error, object::Object, range::Range};
+
+use super::{
+    evaluator::{call, metamethod},
+    generator::{self, Generator},
+};
+
+#[derive(Debug)]
+pub(crate) enum Iter {
+    Range(Range, u128),
+    Values(IntoIter<Object>),
+    Generator(Rc<RefCell<Generator>>),
+    Object(Object, Object),
+    Shared(Rc<RefCell<Iter>>),
+    Map(Box<Iter>, Object),
+    Filter(Box<Iter>, Object),
+    Take(Box<Iter>, usize),
+    Skip(Box<Iter>, usize),
+    Zip(Box<Iter>, Box<Iter>),
+    Enumerate(Box<Iter>, i64),
+    Chain(Box<Iter>, Box<Iter>),
+    FlatMap(Box<Iter>, Object, Option<Box<Iter>>),
+}
+
+impl Iter {
+    pub(crate) fn new(object: Object) -> Result<Iter, Error> {
+        if let Some(next) = metamethod(&object, "__next") {
+            return Ok(Iter::Object(object, next));
+        }
+        match object {
+            Object::Range(range) => Ok(Iter::Range(range, 0)),
+            Object::Array(values) => Ok(Iter::Values(values.into_iter())),
+            Object::String(value) => Ok(Iter::Values(
+                value
+                    .chars()
+                    .map(|character| Object::String(character.to_string()))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            )),
+            Object::Hash(pairs) => Ok(Iter::Values(
+                pairs.keys().cloned().collect::<Vec<_>>().into_iter(),
+            )),
+            Object::Generator(generator) => Ok(Iter::Generator(generator)),
+            Object::Iterator(iterator) => Ok(Iter::Shared(iterator)),
+            object => Err(Error::Runtime(format!("Can not iterate over '{object}'"))),
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Result<Option<Object>, Error> {
+        match self {
+            Iter::Range(range, index) => {
+                let value = range.get(*index).map(Object::Integer);
+                *index += 1;
+                Ok(value)
+            }
+            Iter::Values(values) => Ok(values.next()),
+            Iter::Generator(generator) => generator::resume(generator),
+            Iter::Object(object, next) => {
+                match call(next.to_owned(), vec![object.to_owned()], vec![])? {
+                    Object::Error(error) if error.kind == "StopIteration" => Ok(None),
+                    value => Ok(Some(value)),
+                }
+            }
+            Iter::Shared(iterator) => next(iterator),
+            Iter::Map(source, function) => match source.next()? {
+                Some(value) => Ok(Some(call(function.to_owned(), vec![value], vec![])?)),
+                None => Ok(None),
+            },
+            Iter::Filter(source, predicate) => {
+                while let Some(value) = source.next()? {
+                    if call(predicate.to_owned(), vec![value.to_owned()], vec![])?.is_truthy() {
+                        return Ok(Some(value));
+                    }
+                }
+                Ok(None)
+            }
+            Iter::Take(source, remaining) => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                source.next()
+            }
+            Iter::Skip(source, remaining) => {
+                while *remaining > 0 {
+                    *remaining -= 1;
+                    if source.next()?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                source.next()
+            }
+            Iter::Zip(left, right) => match (left.next()?, right.next()?) {
+                (Some(left), Some(right)) => Ok(Some(Object::Array(vec![left, right].into()))),
+                _ => Ok(None),
+            },
+            Iter::Enumerate(source, index) => match source.next()? {
+                Some(value) => {
+                    let pair = Object::Array(vec![Object::Integer(*index), value].into());
+                    *index += 1;
+                    Ok(Some(pair))
+                }
+                None => Ok(None),
+            },
+            Iter::Chain(first, second) => match first.next()? {
+                Some(value) => Ok(Some(value)),
+                None => second.next(),
+            },
+            Iter::FlatMap(source, function, current) => loop {
+                if let Some(inner) = current {
+                    if let Some(value) = inner.next()? {
+                        return Ok(Some(value));
+                    }
+                }
+                match source.next()? {
+                    Some(value) => {
+                        let inner = call(function.to_owned(), vec![value], vec![])?;
+                        *current = Some(Box::new(Iter::new(inner)?));
+                    }
+                    None => return Ok(None),
+                }
+            },
+        }
+    }
+
+    pub(crate) fn collect(mut self) -> Result<Vec<Object>, Error> {
+        let mut values = vec![];
+        while let Some(value) = self.next()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+pub(crate) fn next(iterator: &Rc<RefCell<Iter>>) -> Result<Option<Object>, Error> {
+    iterator
+        .try_borrow_mut()
+        .map_err(|_| Error::Runtime("Iterator is already running".to_string()))?
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::evaluator::run;
+
+    #[test]
+    fn take_is_lazy_over_a_huge_range() {
+        let source = "
+            let calls = 0
+            let squares = map(0..9223372036854775807, |x| { mut calls = calls + 1  x * x })
+            array[collect(take(squares, 3)), calls]
+        ";
+        assert_eq!(run(source).unwrap(), "array[array[0, 1, 4], 3]");
+    }
+
+    #[test]
+    fn zip_stops_at_the_shorter_side() {
+        let source = "
+            array[
+                collect(zip(array[1, 2, 3], \"xy\")),
+                collect(zip(1..9223372036854775807, array[\"a\"]))
+            ]
+        ";
+        assert_eq!(
+            run(source).unwrap(),
+            "array[array[array[1, \"x\"], array[2, \"y\"]], array[array[1, \"a\"]]]"
+        );
+    }
+
+    #[test]
+    fn flat_map_flattens_one_level() {
+        let source = "array[collect(flat_map(1..4, |n| 0..n)), collect(flat_map(array[\"ab\", \"c\"], chars))]";
+        assert_eq!(
+            run(source).unwrap(),
+            "array[array[0, 0, 1, 0, 1, 2], array[\"a\", \"b\", \"c\"]]"
+        );
+    }
+
+    #[test]
+    fn next_metamethod_ends_on_stop_iteration() {
+        let source = "
+            let n = 0
+            let source = hash[\"__next\": |self| {
+                mut n = n + 1
+                if n == 2 { return 0 }
+                if n <= 3 { n } else { stop_iteration() }
+            }]
+            collect(source)
+        ";
+        assert_eq!(run(source).unwrap(), "array[1, 0, 3]");
+    }
+}
@@ -1,3 +1,5 @@
 pub(crate) mod builtin;
 pub(crate) mod environment;
 pub(crate) mod evaluator;
+pub(crate) mod generator;
+pub(crate) mod iterator;
@@ -8,7 +8,7 @@ use crate::{
 
 pub fn run(path: &str) {
     let environment = Rc::new(RefCell::new(get_builtin()));
-    let source = read_to_string(path).expect(&format!("Could not read from '{path}'"));
+    let source = read_to_string(path).unwrap_or_else(|_| panic!("Could not read from '{path}'"));
     let mut lexer = Lexer::new(source);
     let tokens = lexer.lex().unwrap_or_else(|err| {
         err.report();